@@ -24,6 +24,14 @@ impl DataWrapper {
         };
         DataWrapper { data: option }
     }
+
+    // 선택 옵션이 빠지면 순서가 밀리므로 이름으로 찾음
+    pub fn from_name(options: &[CommandDataOption], name: &str) -> DataWrapper {
+        let option = options.iter()
+            .find(|option| option.name == name)
+            .and_then(|option| option.resolved.to_owned());
+        DataWrapper { data: option }
+    }
 }
 
 impl From<DataWrapper> for Option<String> {
@@ -5,6 +5,7 @@ use serenity::{
     model::{
         application::interaction::{
             application_command::ApplicationCommandInteraction, InteractionResponseType,
+            autocomplete::AutocompleteInteraction,
         },
         id::GuildId,
         prelude::{
@@ -22,8 +23,13 @@ use crate::{
         commands, 
         command_return::{CommandReturn, ControlInteraction}
     }, 
+    database_handler::search_titles,
+    DBContainer,
 };
 
+// discord가 허용하는 자동완성 후보 최대 개수
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+
 use std::collections::HashMap;
 use std::sync::Arc;
 use lazy_static::lazy_static;
@@ -41,6 +47,12 @@ pub trait CommandInterface {
         &'a self,
         command: &'a mut CreateApplicationCommand
     ) -> &'b mut CreateApplicationCommand;
+    // 자동완성 옵션이 있는 명령어만 구현
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _autocomplete: &AutocompleteInteraction
+    ) {}
 }
 
 pub struct CommandList {
@@ -68,10 +80,38 @@ lazy_static! {
             ("퇴장", commands::disconnect::command()),
             ("재생", commands::play::command()),
             ("곡추가", commands::add_song::command()),
+            ("별칭추가", commands::add_alias::command()),
+            ("별칭삭제", commands::remove_alias::command()),
+            ("제목목록", commands::title_list::command()),
+            ("곡삭제", commands::remove_song::command()),
+            ("곡목록", commands::song_list::command()),
+            ("곡일괄추가", commands::import_songs::command()),
+            ("곡내보내기", commands::export_songs::command()),
+            ("곡공유", commands::share_song::command()),
+            ("공유풀", commands::shared_pool::command()),
+            ("기존곡가져오기", commands::claim_songs::command()),
             ("큐잉", commands::insert_queue::command()),
             ("큐재생", commands::play_queue::command()),
             ("큐확인", commands::check_queue::command()),
             ("스탯", commands::stat::command()),
+            ("연도설정", commands::set_year::command()),
+            ("구간설정", commands::set_clip::command()),
+            ("가수추가", commands::add_artist::command()),
+            ("가사설정", commands::set_lyrics::command()),
+            ("싱크가사", commands::set_synced_lyrics::command()),
+            ("태그추가", commands::add_tag::command()),
+            ("프로필", commands::profile::command()),
+            ("랭킹", commands::ranking::command()),
+            ("시즌", commands::season::command()),
+            ("시즌채널", commands::season_channel::command()),
+            ("업적", commands::achievements::command()),
+            ("점수규칙", commands::scoring_rule::command()),
+            ("연대순", commands::chronology::command()),
+            ("퀴즈팩", commands::quiz_pack::command()),
+            ("퀴즈시작", commands::quiz_start::command()),
+            ("도전", commands::challenge::command()),
+            ("도전시작", commands::challenge_play::command()),
+            ("도전받기", commands::accept_challenges::command()),
         ])
     };
 }
@@ -116,8 +156,49 @@ pub async fn execute_command(ctx: &Context, command: ApplicationCommandInteracti
                 error!("{:#?}", why);
             }
         }
-        _ => command.delete_original_interaction_response(&ctx.http).await.unwrap(),
+        _ => {
+            // 오래 걸린 명령어는 interaction 토큰이 만료되어 지우지 못할 수 있음
+            if let Err(why) = command.delete_original_interaction_response(&ctx.http).await {
+                error!("Failed to delete response from command \"{}\".", command.data.name);
+                error!("{:#?}", why);
+            }
+        },
     }
 
 }
 
+
+pub async fn execute_autocomplete(ctx: &Context, autocomplete: AutocompleteInteraction) {
+    if let Some(command) = COMMAND_LIST.commands.get(autocomplete.data.name.as_str()) {
+        command.autocomplete(ctx, &autocomplete).await;
+    }
+}
+
+// 입력 중인 값이 들어간 DB 제목을 후보로 보여줌
+pub async fn autocomplete_titles(ctx: &Context, autocomplete: &AutocompleteInteraction) {
+    let query = autocomplete.data.options.iter()
+        .find(|option| option.focused)
+        .and_then(|option| option.value.as_ref())
+        .and_then(|value| value.as_str())
+        .unwrap_or_default()
+        .to_owned();
+
+    let guild_id = match autocomplete.guild_id {
+        Some(guild_id) => guild_id.0,
+        None => return,
+    };
+    let titles = {
+        let data = ctx.data.read().await;
+        let conn = data.get::<DBContainer>().unwrap();
+        search_titles(conn, guild_id, query, MAX_AUTOCOMPLETE_CHOICES).await.unwrap_or_default()
+    };
+
+    if let Err(why) = autocomplete.create_autocomplete_response(&ctx.http, |response| {
+        for title in titles.iter() {
+            response.add_string_choice(title, title);
+        }
+        response
+    }).await {
+        error!("failed to respond autocomplete {:#?}", why);
+    }
+}
@@ -0,0 +1,68 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+        Permissions,
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct AcceptChallenges;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(AcceptChallenges)
+}
+
+#[async_trait]
+impl CommandInterface for AcceptChallenges {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let accept = match Option::<bool>::from(DataWrapper::from(options, 0)) {
+            Some(accept) => accept,
+            None => return CommandReturn::String("받을지 여부를 선택해주세요.".to_string()),
+        };
+
+        let gid = command.guild_id.unwrap();
+        match set_accept_challenges(&ctx.data.read().await.get::<DBContainer>().unwrap(), gid.0, accept).await {
+            Ok(_) if accept => CommandReturn::String(format!("이제 다른 서버가 이 서버에 도전할 수 있습니다. (서버 ID: {})", gid.0)),
+            Ok(_) => CommandReturn::String("이제 다른 서버의 도전을 받지 않습니다.".to_string()),
+            Err(why) => CommandReturn::String(format!("오류로 인해 설정이 바뀌지 않았습니다. ({})", why)),
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("도전받기")
+            .description("다른 서버가 이 서버에 대항전을 신청할 수 있는지 정합니다. (기본 끔)")
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .create_option(|option| {
+                option
+                    .name("받기")
+                    .description("도전을 받으려면 True")
+                    .kind(CommandOptionType::Boolean)
+                    .required(true)
+            })
+    }
+}
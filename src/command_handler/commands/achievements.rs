@@ -0,0 +1,90 @@
+use serenity::{
+    async_trait,
+    builder::{CreateApplicationCommand, CreateEmbed},
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+        user::User,
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    quiz_handler::achievement::Achievement,
+    database_handler::*, DBContainer,
+};
+
+struct Achievements;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(Achievements)
+}
+
+#[async_trait]
+impl CommandInterface for Achievements {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let user = Option::<User>::from(DataWrapper::from(options, 0)).unwrap_or_else(|| command.user.clone());
+
+        let data = ctx.data.read().await;
+        let conn = data.get::<DBContainer>().unwrap();
+        // 기록이 없어도 잠긴 업적 목록은 보여줌
+        let progress = achievement_progress(conn, user.id.0).await.unwrap_or(AchievementProgress {
+            correct: 0,
+            best_streak: 0,
+            fastest_ms: None,
+            wins: 0,
+        });
+        let unlocked = unlocked_achievements(conn, user.id.0).await.unwrap_or_default();
+
+        let mut earned = vec![];
+        let mut locked = vec![];
+        for achievement in Achievement::ALL.iter() {
+            match unlocked.iter().find(|(id, _)| id == achievement.id()) {
+                Some((_, unlocked_at)) => {
+                    let date = unlocked_at.split('T').next().unwrap_or_default();
+                    earned.push(format!("🏆 **{}** - {} ({})", achievement.name(), achievement.description(), date));
+                },
+                None => {
+                    let (current, goal) = achievement.progress(&progress);
+                    locked.push(format!("🔒 **{}** - {} ({}/{})", achievement.name(), achievement.description(), current, goal));
+                },
+            }
+        }
+
+        let mut embed = CreateEmbed::default();
+        embed.title(format!("{}의 업적 ({}/{})", user.name, earned.len(), Achievement::ALL.len()))
+            .field("달성", if earned.is_empty() { "-".to_owned() } else { earned.join("\n") }, false)
+            .field("미달성", if locked.is_empty() { "-".to_owned() } else { locked.join("\n") }, false);
+        CommandReturn::SingleEmbed(embed)
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("업적")
+            .description("달성한 업적과 남은 업적의 진행도를 보여줍니다.")
+            .create_option(|option| {
+                option
+                    .name("유저")
+                    .description("업적을 볼 유저 (기본값: 본인)")
+                    .kind(CommandOptionType::User)
+                    .required(false)
+            })
+    }
+}
@@ -0,0 +1,93 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction,
+            autocomplete::AutocompleteInteraction,
+        },
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct AddAlias;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(AddAlias)
+}
+
+#[async_trait]
+impl CommandInterface for AddAlias {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let title = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(title) => title,
+            None => return CommandReturn::String("제목을 입력해주세요.".to_string()),
+        };
+
+        let alias = match Option::<String>::from(DataWrapper::from(options, 1)) {
+            Some(alias) => alias,
+            None => return CommandReturn::String("별칭을 입력해주세요.".to_string()),
+        };
+
+        match add_alias_with_title(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, title.to_owned(), alias.to_owned()).await {
+            Ok(_) => CommandReturn::String(format!("{}의 별칭으로 {}을 추가했습니다.", title, alias)),
+            Err(why) => match why {
+                DBError::TitleAlreadyUsed => CommandReturn::String("이미 사용 중인 제목입니다.".to_string()),
+                DBError::TitleNotFound => CommandReturn::String("DB에 없는 제목입니다.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡에만 별칭을 추가할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 별칭이 추가되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("별칭추가")
+            .description("곡에 다른 정답(별칭)을 추가합니다.")
+            .create_option(|option| {
+                option
+                    .name("제목")
+                    .description("별칭을 추가할 곡의 제목")
+                    .kind(CommandOptionType::String)
+                    .set_autocomplete(true)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("별칭")
+                    .description("정답으로 인정할 다른 제목")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+    }
+
+    async fn autocomplete(
+        &self,
+        ctx: &Context,
+        autocomplete: &AutocompleteInteraction
+    ) {
+        autocomplete_titles(ctx, autocomplete).await;
+    }
+}
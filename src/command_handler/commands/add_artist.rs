@@ -0,0 +1,86 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::url_checker::{url_checker},
+    database_handler::*, DBContainer,
+};
+
+struct AddArtist;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(AddArtist)
+}
+
+#[async_trait]
+impl CommandInterface for AddArtist {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let url = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(url) => url,
+            None => return CommandReturn::String("url을 입력해주세요.".to_string()),
+        };
+
+        let artist = match Option::<String>::from(DataWrapper::from(options, 1)) {
+            Some(artist) => artist,
+            None => return CommandReturn::String("가수를 입력해주세요.".to_string()),
+        };
+
+        let url = match url_checker(&url) {
+            Some(url) => url,
+            None => return CommandReturn::String("유효한 url이 아닙니다.".to_string()),
+        };
+
+        match add_artist(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, url, artist.to_owned()).await {
+            Ok(_) => CommandReturn::String(format!("가수 {}를 추가했습니다.", artist)),
+            Err(why) => match why {
+                DBError::UrlNotFound => CommandReturn::String("DB에 없는 곡입니다. 먼저 곡을 추가해주세요.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡에만 가수를 추가할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 가수가 추가되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("가수추가")
+            .description("DB에 있는 곡에 가수 정답을 추가합니다.")
+            .create_option(|option| {
+                option
+                    .name("url")
+                    .description("가수를 추가할 노래 url")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("가수")
+                    .description("정답으로 인정할 가수 이름")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+    }
+}
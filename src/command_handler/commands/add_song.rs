@@ -32,7 +32,10 @@ use crate::{
         command_data::*,
         command_return::CommandReturn,
     },
-    utils::url_checker::{url_checker},
+    utils::{
+        url_checker::{url_checker},
+        audio_module::youtube_dl::spawn_metadata_fill,
+    },
     database_handler::*, DBContainer,
 };
 
@@ -67,10 +70,12 @@ impl CommandInterface for AddSong {
             None => return CommandReturn::String("유효한 url이 아닙니다.".to_string()),
         };
 
-        match add_title_with_url(&ctx.data.read().await.get::<DBContainer>().unwrap(), url.to_owned(), title.to_owned()).await {
+        let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+        match add_title_with_url(&conn, gid.0, url.to_owned(), title.to_owned()).await {
             Ok(success) => {
                 match success {
                     DBSuccess::NewUrl => {
+                        spawn_metadata_fill(conn, vec![url]);
                         CommandReturn::String(format!("새 곡 {}이 DB에 추가되었습니다.", title))
                     },
                     DBSuccess::ExistUrl => {
@@ -83,7 +88,10 @@ impl CommandInterface for AddSong {
                     DBError::TitleAlreadyUsed => {
                         CommandReturn::String(format!("이미 사용 중인 제목입니다."))
                     },
-                    _ => CommandReturn::String(format!("오류로 인해 곡이 등록되지 않았습니다."))
+                    DBError::NotOwner => {
+                        CommandReturn::String(format!("다른 서버가 등록한 곡에는 제목을 추가할 수 없습니다. 서버별 곡 관리 이전에 등록된 곡이라면 /기존곡가져오기로 먼저 가져와주세요."))
+                    },
+                    why => CommandReturn::String(format!("오류로 인해 곡이 등록되지 않았습니다. ({})", why))
                 }
             },
         }
@@ -0,0 +1,86 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::url_checker::{url_checker},
+    database_handler::*, DBContainer,
+};
+
+struct AddTag;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(AddTag)
+}
+
+#[async_trait]
+impl CommandInterface for AddTag {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let url = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(url) => url,
+            None => return CommandReturn::String("url을 입력해주세요.".to_string()),
+        };
+
+        let tag = match Option::<String>::from(DataWrapper::from(options, 1)) {
+            Some(tag) => tag,
+            None => return CommandReturn::String("태그를 입력해주세요.".to_string()),
+        };
+
+        let url = match url_checker(&url) {
+            Some(url) => url,
+            None => return CommandReturn::String("유효한 url이 아닙니다.".to_string()),
+        };
+
+        match add_tag(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, url, tag.to_owned()).await {
+            Ok(_) => CommandReturn::String(format!("태그 {}를 추가했습니다.", tag)),
+            Err(why) => match why {
+                DBError::UrlNotFound => CommandReturn::String("DB에 없는 곡입니다. 먼저 곡을 추가해주세요.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡에만 태그를 추가할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 태그가 추가되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("태그추가")
+            .description("DB에 있는 곡에 태그를 추가합니다. 프로필의 선호 태그에 사용됩니다.")
+            .create_option(|option| {
+                option
+                    .name("url")
+                    .description("태그를 추가할 노래 url")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("태그")
+                    .description("장르, 시대 등 곡을 분류할 태그")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+    }
+}
@@ -0,0 +1,147 @@
+use chrono::{Duration, Utc};
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+        Permissions,
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+        commands::quiz_start::random_entries,
+    },
+    quiz_handler::{
+        challenge::{announce_channel, deadline_text, guild_name},
+        game_code::save_entries,
+        quiz_session::QuizSettings,
+    },
+    database_handler::*, DBContainer,
+};
+
+const DEFAULT_SONG_COUNT: i64 = 10;
+const MAX_SONG_COUNT: i64 = 30;
+const DEFAULT_WINDOW_HOURS: i64 = 24;
+const MAX_WINDOW_HOURS: i64 = 72;
+
+struct Challenge;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(Challenge)
+}
+
+#[async_trait]
+impl CommandInterface for Challenge {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let guest = match Option::<String>::from(DataWrapper::from(options, 0)).and_then(|id| id.trim().parse::<u64>().ok()) {
+            Some(guest) => guest,
+            None => return CommandReturn::String("서버 ID를 입력해주세요.".to_string()),
+        };
+        let count = Option::<i64>::from(DataWrapper::from_name(options, "곡수"))
+            .unwrap_or(DEFAULT_SONG_COUNT)
+            .clamp(1, MAX_SONG_COUNT) as usize;
+        let hours = Option::<i64>::from(DataWrapper::from_name(options, "시간"))
+            .unwrap_or(DEFAULT_WINDOW_HOURS)
+            .clamp(1, MAX_WINDOW_HOURS);
+
+        let host = command.guild_id.unwrap().0;
+        if guest == host {
+            return CommandReturn::String("다른 서버에만 도전할 수 있습니다.".to_string());
+        }
+        if ctx.cache.guild(guest).is_none() {
+            return CommandReturn::String("봇이 참여하지 않은 서버입니다.".to_string());
+        }
+
+        // data 잠금을 잡은 채 메시지를 보내지 않도록 연결을 복사해서 씀
+        let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+
+        // 봇만 같이 있으면 아무 서버에나 보낼 수 있으므로 상대 서버가 직접 켜야 받음
+        if !accepts_challenges(&conn, guest).await {
+            return CommandReturn::String("상대 서버가 도전을 받지 않습니다. 상대 서버 관리자가 /도전받기로 켜야 합니다.".to_string());
+        }
+
+        // 두 서버가 같은 곡, 같은 구간을 듣도록 게임 코드로 고정
+        let entries = match random_quiz_songs(&conn, host, count).await {
+            Ok(songs) => random_entries(songs, false, false, false, false),
+            Err(_) => return CommandReturn::String("곡을 불러오지 못했습니다.".to_string()),
+        };
+        let code = match save_entries(&conn, &entries, &QuizSettings::default()).await {
+            Some(code) => code,
+            None => return CommandReturn::String("DB에 곡이 없습니다.".to_string()),
+        };
+
+        let deadline = (Utc::now() + Duration::hours(hours)).to_rfc3339();
+        let id = match create_challenge(&conn, code, host, guest, deadline.to_owned()).await {
+            Ok(id) => id,
+            Err(_) => return CommandReturn::String("오류로 인해 도전을 만들지 못했습니다.".to_string()),
+        };
+
+        let host_name = guild_name(ctx, host);
+        if let Some(channel_id) = announce_channel(ctx, &conn, guest).await {
+            if let Err(why) = channel_id.send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(format!("⚔️ {}의 도전장!", host_name))
+                        .description(format!("`/도전시작 번호:{}`으로 같은 {}곡을 플레이해 결과를 겨뤄보세요.", id, entries.len()))
+                        .field("마감", deadline_text(&deadline), true)
+                })
+            }).await {
+                println!("failed to deliver challenge {:?}", why);
+            }
+        }
+
+        CommandReturn::String(format!(
+            "{}에 도전 #{}을 보냈습니다. `/도전시작 번호:{}`으로 플레이해주세요. (마감 {})",
+            guild_name(ctx, guest), id, id, deadline_text(&deadline)
+        ))
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("도전")
+            .description("다른 서버에 같은 곡으로 겨루는 대항전을 신청합니다. 상대 서버가 /도전받기를 켜야 합니다.")
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .create_option(|option| {
+                option
+                    .name("서버")
+                    .description("도전할 서버의 ID")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("곡수")
+                    .description("출제할 곡 수")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .max_int_value(MAX_SONG_COUNT)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("시간")
+                    .description("두 서버가 플레이할 수 있는 기간(시간)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .max_int_value(MAX_WINDOW_HOURS)
+                    .required(false)
+            })
+    }
+}
@@ -0,0 +1,108 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    quiz_handler::{
+        challenge::is_expired,
+        game_code::load_challenge_code,
+        quiz_session::QuizSession,
+    },
+    database_handler::*, DBContainer,
+    connection_handler::*,
+};
+
+struct ChallengePlay;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(ChallengePlay)
+}
+
+#[async_trait]
+impl CommandInterface for ChallengePlay {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let id = match Option::<i64>::from(DataWrapper::from(options, 0)) {
+            Some(id) => id as u64,
+            None => return CommandReturn::String("도전 번호를 입력해주세요.".to_string()),
+        };
+        let gid = command.guild_id.unwrap();
+
+        let (challenge, entries, settings) = {
+            let data = ctx.data.read().await;
+            let conn = data.get::<DBContainer>().unwrap();
+            let challenge = match challenge_info(conn, id).await {
+                Ok(challenge) => challenge,
+                Err(_) => return CommandReturn::String("존재하지 않는 도전입니다.".to_string()),
+            };
+            if challenge.host_guild != gid.0 && challenge.guest_guild != gid.0 {
+                return CommandReturn::String("이 서버가 참가하는 도전이 아닙니다.".to_string());
+            }
+            if is_expired(&challenge) {
+                return CommandReturn::String("이미 마감된 도전입니다.".to_string());
+            }
+            let played = challenge_results(conn, id).await.unwrap_or_default();
+            if played.iter().any(|result| result.guild_id == gid.0) {
+                return CommandReturn::String("이 서버는 이미 도전을 플레이했습니다.".to_string());
+            }
+            match load_challenge_code(conn, &challenge).await {
+                Ok((entries, settings)) => (challenge, entries, settings),
+                Err(why) => return CommandReturn::String(why),
+            }
+        };
+
+        match establish_connection(ctx, command).await {
+            Ok(_) => (),
+            Err(why) => match why {
+                ConnectionErrorCode::JoinVoiceChannelFirst => return CommandReturn::String("음성채널에 먼저 접속해주세요.".to_owned()),
+                ConnectionErrorCode::AlreadyInUse => return CommandReturn::String("다른 채널에서 사용중입니다.".to_owned()),
+                _ => return CommandReturn::String("연결에 실패했습니다.".to_owned()),
+            },
+        };
+
+        let mut session = QuizSession::new(gid, command.channel_id, entries, settings);
+        session.code = Some(challenge.code);
+        session.challenge = Some(challenge.id);
+        match session.run(ctx).await {
+            Some(embed) => CommandReturn::SingleEmbed(embed),
+            // 최종 순위는 채널에 올라가므로 원래 응답은 지움
+            None => CommandReturn::None,
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("도전시작")
+            .description("서버 대항전 곡으로 퀴즈를 시작합니다.")
+            .create_option(|option| {
+                option
+                    .name("번호")
+                    .description("도전 번호")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .required(true)
+            })
+    }
+}
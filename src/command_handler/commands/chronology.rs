@@ -0,0 +1,69 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_return::CommandReturn,
+    },
+    quiz_handler::chronology::{run_chronology_round, CLIP_COUNT},
+    database_handler::*, DBContainer,
+    connection_handler::*,
+};
+
+struct Chronology;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(Chronology)
+}
+
+#[async_trait]
+impl CommandInterface for Chronology {
+    async fn run(
+        &self, 
+        ctx: &Context, 
+        command: &ApplicationCommandInteraction, 
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        match establish_connection(ctx, command).await {
+            Ok(_) => (),
+            Err(why) => match why {
+                ConnectionErrorCode::JoinVoiceChannelFirst => return CommandReturn::String("음성채널에 먼저 접속해주세요.".to_owned()),
+                ConnectionErrorCode::AlreadyInUse => return CommandReturn::String("다른 채널에서 사용중입니다.".to_owned()),
+                _ => return CommandReturn::String("연결에 실패했습니다.".to_owned()),
+            },
+        };
+
+        let gid = command.guild_id.unwrap();
+        let songs = match random_dated_songs(&ctx.data.read().await.get::<DBContainer>().unwrap(), gid.0, CLIP_COUNT).await {
+            Ok(songs) => songs,
+            Err(_) => return CommandReturn::String("곡을 불러오지 못했습니다.".to_owned()),
+        };
+
+        if songs.len() < CLIP_COUNT {
+            return CommandReturn::String(format!("발매연도가 등록된 곡이 {}곡 이상 필요합니다.", CLIP_COUNT));
+        }
+
+        let embed = run_chronology_round(ctx, gid, command.channel_id, &songs).await;
+        CommandReturn::SingleEmbed(embed)
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("연대순")
+            .description("4곡을 듣고 발매연도 순서를 맞춥니다.")
+    }
+}
@@ -0,0 +1,74 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+        Permissions,
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct ClaimSongs;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(ClaimSongs)
+}
+
+#[async_trait]
+impl CommandInterface for ClaimSongs {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        // 제목을 안 주면 남은 예전 곡 전체를 가져옴
+        let title = Option::<String>::from(DataWrapper::from_name(options, "제목"));
+
+        let gid = command.guild_id.unwrap();
+        let report = match claim_legacy_songs(&ctx.data.read().await.get::<DBContainer>().unwrap(), gid.0, title).await {
+            Ok(report) => report,
+            Err(why) => match why {
+                DBError::TitleNotFound => return CommandReturn::String("가져올 수 있는 예전 곡 중에 없는 제목입니다.".to_string()),
+                why => return CommandReturn::String(format!("오류로 인해 곡을 가져오지 못했습니다. ({})", why)),
+            },
+        };
+
+        let mut msg = format!("예전 곡 {}곡을 이 서버 곡으로 가져왔습니다. 공유 풀에도 그대로 남습니다.", report.claimed);
+        if !report.conflicts.is_empty() {
+            msg += &format!("\n이 서버 곡과 제목이 겹쳐 가져오지 못한 곡: {}", report.conflicts.join(", "));
+        }
+        CommandReturn::String(msg)
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("기존곡가져오기")
+            .description("서버별 곡 관리 이전에 등록된 공용 곡을 이 서버 곡으로 가져와 수정, 삭제할 수 있게 합니다.")
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .create_option(|option| {
+                option
+                    .name("제목")
+                    .description("가져올 곡의 제목 (비우면 남은 예전 곡 전체)")
+                    .kind(CommandOptionType::String)
+                    .required(false)
+            })
+    }
+}
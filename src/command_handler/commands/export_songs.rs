@@ -0,0 +1,104 @@
+use std::borrow::Cow;
+
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        channel::AttachmentType,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::song_file::{write_song_file, SongFileFormat},
+    database_handler::*, DBContainer,
+};
+
+const EXPORT_FILENAME: &str = "music_library";
+
+struct ExportSongs;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(ExportSongs)
+}
+
+#[async_trait]
+impl CommandInterface for ExportSongs {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let extension = Option::<String>::from(DataWrapper::from_name(options, "형식")).unwrap_or("json".to_owned());
+        let filename = format!("{}.{}", EXPORT_FILENAME, extension);
+        let format = match SongFileFormat::from_filename(&filename) {
+            Some(format) => format,
+            None => return CommandReturn::String("지원하지 않는 형식입니다.".to_string()),
+        };
+
+        // 기본은 이 서버가 등록한 곡만
+        let include_shared = Option::<bool>::from(DataWrapper::from_name(options, "공유포함")).unwrap_or(false);
+        let rows = match export_songs(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, include_shared).await {
+            Ok(rows) => rows,
+            Err(why) => return CommandReturn::String(format!("오류로 인해 곡 목록을 불러오지 못했습니다. ({})", why)),
+        };
+        if rows.is_empty() {
+            return CommandReturn::String("DB에 곡이 없습니다.".to_string());
+        }
+        let count = rows.len();
+
+        let data = match write_song_file(format, rows) {
+            Ok(data) => data,
+            Err(why) => return CommandReturn::String(format!("파일을 만들지 못했습니다. ({})", why)),
+        };
+
+        // 처음 응답은 수정으로 파일을 붙일 수 없으므로 후속 메시지로 보냄
+        if let Err(why) = command.create_followup_message(&ctx.http, |m| {
+            m.add_file(AttachmentType::Bytes {
+                data: Cow::Owned(data),
+                filename,
+            })
+        }).await {
+            println!("failed to send export file {:?}", why);
+            return CommandReturn::String("파일을 보내지 못했습니다.".to_string());
+        }
+        CommandReturn::String(format!("{}곡을 내보냈습니다. /곡일괄추가로 다시 불러올 수 있습니다.", count))
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("곡내보내기")
+            .description("서버의 곡을 /곡일괄추가로 불러올 수 있는 파일로 내보냅니다.")
+            .create_option(|option| {
+                option
+                    .name("형식")
+                    .description("파일 형식 (기본 JSON)")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("JSON", "json")
+                    .add_string_choice("CSV", "csv")
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("공유포함")
+                    .description("공유 풀의 곡(소유 서버가 없는 기존 곡 포함)까지 포함")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+    }
+}
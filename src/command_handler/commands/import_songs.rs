@@ -0,0 +1,121 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        channel::Attachment,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::{
+        song_file::{parse_song_file, SongFileFormat},
+        audio_module::youtube_dl::spawn_metadata_fill,
+    },
+    database_handler::*, DBContainer,
+};
+
+const MAX_IMPORT_SIZE: u64 = 1024 * 1024;
+// 결과 메시지에 보여줄 건너뛴 줄 수
+const MAX_REPORT_ITEMS: usize = 10;
+
+struct ImportSongs;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(ImportSongs)
+}
+
+#[async_trait]
+impl CommandInterface for ImportSongs {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let attachment = match Option::<Attachment>::from(DataWrapper::from(options, 0)) {
+            Some(attachment) => attachment,
+            None => return CommandReturn::String("파일을 첨부해주세요.".to_string()),
+        };
+        let format = match SongFileFormat::from_filename(&attachment.filename) {
+            Some(format) => format,
+            None => return CommandReturn::String("CSV나 JSON 파일만 사용할 수 있습니다.".to_string()),
+        };
+        if attachment.size > MAX_IMPORT_SIZE {
+            return CommandReturn::String("파일이 너무 큽니다.".to_string());
+        }
+        let data = match attachment.download().await {
+            Ok(data) => data,
+            Err(_) => return CommandReturn::String("파일을 받지 못했습니다.".to_string()),
+        };
+
+        let (rows, invalid) = match parse_song_file(format, &data) {
+            Ok(parsed) => parsed,
+            Err(why) => return CommandReturn::String(why),
+        };
+
+        let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+        let report = match import_songs(&conn, command.guild_id.unwrap().0, rows).await {
+            Ok(report) => report,
+            Err(why) => return CommandReturn::String(format!("오류로 인해 아무 곡도 등록되지 않았습니다. ({})", why)),
+        };
+        let mut lines = vec![format!(
+            "추가 {}곡, 중복 {}곡, 잘못된 줄 {}개",
+            report.added.len(), report.duplicates.len(), invalid.len()
+        )];
+        // 이번에 추가한 곡의 메타데이터만 받음
+        if !report.added.is_empty() {
+            spawn_metadata_fill(conn, report.added);
+        }
+        if !report.duplicates.is_empty() {
+            lines.push(format!("중복: {}", summarize(&report.duplicates)));
+        }
+        if !report.skipped_aliases.is_empty() {
+            lines.push(format!("이미 쓰이고 있어 빠진 별칭 {}개: {}", report.skipped_aliases.len(), summarize(&report.skipped_aliases)));
+        }
+        if !invalid.is_empty() {
+            let numbers = invalid.iter().map(|line| line.to_string()).collect::<Vec<String>>();
+            lines.push(format!("잘못된 줄 번호: {}", summarize(&numbers)));
+        }
+        CommandReturn::String(lines.join("\n"))
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("곡일괄추가")
+            .description("CSV나 JSON 파일로 곡을 한꺼번에 추가합니다.")
+            .create_option(|option| {
+                option
+                    .name("파일")
+                    .description("url, title 열은 필수, /곡내보내기 파일 형식과 같음 (CSV의 목록은 | 로 구분)")
+                    .kind(CommandOptionType::Attachment)
+                    .required(true)
+            })
+    }
+}
+
+fn summarize(items: &[String]) -> String {
+    let mut summary = items.iter()
+        .take(MAX_REPORT_ITEMS)
+        .map(String::as_str)
+        .collect::<Vec<&str>>()
+        .join(", ");
+    if items.len() > MAX_REPORT_ITEMS {
+        summary.push_str(&format!(" 외 {}개", items.len() - MAX_REPORT_ITEMS));
+    }
+    summary
+}
@@ -7,4 +7,32 @@ pub mod add_song;
 pub mod insert_queue;
 pub mod play_queue;
 pub mod check_queue;
-pub mod stat;
\ No newline at end of file
+pub mod stat;
+pub mod chronology;
+pub mod set_year;
+pub mod set_clip;
+pub mod quiz_pack;
+pub mod quiz_start;
+pub mod add_artist;
+pub mod set_lyrics;
+pub mod set_synced_lyrics;
+pub mod add_tag;
+pub mod profile;
+pub mod ranking;
+pub mod season;
+pub mod season_channel;
+pub mod achievements;
+pub mod scoring_rule;
+pub mod challenge;
+pub mod challenge_play;
+pub mod accept_challenges;
+pub mod add_alias;
+pub mod remove_alias;
+pub mod title_list;
+pub mod remove_song;
+pub mod song_list;
+pub mod import_songs;
+pub mod export_songs;
+pub mod share_song;
+pub mod shared_pool;
+pub mod claim_songs;
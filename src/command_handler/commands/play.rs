@@ -36,10 +36,12 @@ use crate::{
             youtube_dl::ytdl_optioned,
             track_event_handler::{TrackEndNotifier, TrackQueuingNotifier},
         },
-        play_info_notifier::{create_play_info_embed, update_play_info_embed}
+        play_info_notifier::{create_play_info_embed, update_play_info_embed, update_play_info_embed_with_lyric},
+        lrc::{parse_lrc, lyric_at},
     },
+    database_handler::synced_lyrics,
     connection_handler::*,
-    GuildQueueContainer
+    GuildQueueContainer, DBContainer
 };
 
 struct Play;
@@ -88,8 +90,14 @@ impl CommandInterface for Play {
             None => return CommandReturn::String("url이 잘못되었습니다.".to_string()),
         };
 
+        let lyrics = match synced_lyrics(ctx.data.read().await.get::<DBContainer>().unwrap(), url.to_owned()).await {
+            Some(lrc) => parse_lrc(&lrc),
+            None => vec![],
+        };
+
         let start = Instant::now();
-        let src = ytdl_optioned(url, start_time, play_time).await.unwrap();
+        let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+        let src = ytdl_optioned(&conn, url, start_time, play_time).await.unwrap();
         let d = start.elapsed();
         println!("{:?} elapsed", d);
 
@@ -141,7 +149,13 @@ impl CommandInterface for Play {
         while let Ok(playing_info) = audio_handle.get_info().await {
             current_time = playing_info.position;
             if current_time - last_edit_time >= Duration::from_millis(900) {
-                embed = update_play_info_embed(embed, title.as_ref(), current_time.as_secs()+1, duration);
+                embed = if lyrics.is_empty() {
+                    update_play_info_embed(embed, title.as_ref(), current_time.as_secs()+1, duration)
+                } else {
+                    // 시작시간만큼 잘라서 재생하므로 곡 기준 위치는 그만큼 더해야 함
+                    let lyric = lyric_at(&lyrics, current_time + Duration::from_secs(start_time));
+                    update_play_info_embed_with_lyric(embed, title.as_ref(), current_time.as_secs()+1, duration, lyric)
+                };
                 msg.edit(&ctx.http, |m| {
                     m.embed(|e| {
                         e.clone_from(&embed);
@@ -49,7 +49,7 @@ use crate::{
         },
         play_info_notifier::{create_play_info_embed, update_play_info_embed}, guild_queue::GuildQueue
     },
-    connection_handler::*, GuildQueueContainer,
+    connection_handler::*, GuildQueueContainer, DBContainer,
 };
 
 struct PlayQueue;
@@ -101,7 +101,8 @@ impl CommandInterface for PlayQueue {
             },
             None => match url {
                 Some(url) => {
-                    let src = ytdl_optioned(url, 10, 30).await.unwrap();
+                    let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+                    let src = ytdl_optioned(&conn, url, 10, 30).await.unwrap();
                     let handler_lock = voice_manager.get(gid).unwrap();
                     let mut handler = handler_lock.lock().await;
             
@@ -116,6 +117,7 @@ impl CommandInterface for PlayQueue {
                                 http: ctx.http.clone(),
                                 guild_queue: queue_lock.clone(),
                                 voice_manager: handler_lock.clone(),
+                                conn,
                             }
                         );
                         queue.now_playing = Some(audio_handle);
@@ -0,0 +1,96 @@
+use serenity::{
+    async_trait,
+    builder::{CreateApplicationCommand, CreateEmbed},
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+        user::User,
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct Profile;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(Profile)
+}
+
+#[async_trait]
+impl CommandInterface for Profile {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        // 대상을 안 주면 본인 프로필
+        let user = Option::<User>::from(DataWrapper::from(options, 0)).unwrap_or_else(|| command.user.clone());
+
+        let profile = match player_profile(&ctx.data.read().await.get::<DBContainer>().unwrap(), user.id.0).await {
+            Ok(profile) => profile,
+            Err(why) => match why {
+                DBError::PlayerNotFound => return CommandReturn::String(format!("{}의 퀴즈 기록이 없습니다.", user.name)),
+                _ => return CommandReturn::String("오류로 인해 프로필을 불러오지 못했습니다.".to_string()),
+            },
+        };
+
+        let accuracy = if profile.rounds > 0 {
+            profile.correct as f64 * 100.0 / profile.rounds as f64
+        } else {
+            0.0
+        };
+        let reaction = if profile.correct > 0 {
+            format!("{:.2}초", profile.reaction_ms as f64 / profile.correct as f64 / 1000.0)
+        } else {
+            "-".to_owned()
+        };
+        let tags = if profile.tags.is_empty() {
+            "-".to_owned()
+        } else {
+            profile.tags.iter()
+                .map(|(tag, correct)| format!("{} ({}곡)", tag, correct))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+
+        let mut embed = CreateEmbed::default();
+        embed.title(format!("{}의 프로필", profile.name))
+            .thumbnail(user.face())
+            .field("전체 순위", format!("{}위", profile.rank), true)
+            .field("맞힌 곡", format!("{}곡", profile.correct), true)
+            .field("정답률", format!("{:.1}%", accuracy), true)
+            .field("평균 반응속도", reaction, true)
+            .field("최고 연속 정답", format!("{}연속", profile.best_streak), true)
+            .field("선호 태그", tags, false);
+        CommandReturn::SingleEmbed(embed)
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("프로필")
+            .description("모든 서버에서 쌓인 퀴즈 기록을 보여줍니다.")
+            .create_option(|option| {
+                option
+                    .name("유저")
+                    .description("프로필을 볼 유저 (기본값: 본인)")
+                    .kind(CommandOptionType::User)
+                    .required(false)
+            })
+    }
+}
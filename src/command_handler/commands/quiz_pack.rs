@@ -0,0 +1,130 @@
+use serenity::{
+    async_trait,
+    builder::{CreateApplicationCommand, CreateEmbed},
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        channel::Attachment,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    quiz_handler::{
+        quiz_pack::{load_quiz_pack, QuizPack, MAX_PACK_SIZE},
+        quiz_session::QuizSession,
+    },
+    connection_handler::*,
+};
+
+struct QuizPackCommand;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(QuizPackCommand)
+}
+
+#[async_trait]
+impl CommandInterface for QuizPackCommand {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let attachment = match Option::<Attachment>::from(DataWrapper::from(options, 0)) {
+            Some(attachment) => attachment,
+            None => return CommandReturn::String("퀴즈팩 파일을 첨부해주세요.".to_string()),
+        };
+
+        if attachment.size > MAX_PACK_SIZE {
+            return CommandReturn::String("파일이 너무 큽니다.".to_string());
+        }
+
+        let raw = match attachment.download().await {
+            Ok(raw) => raw,
+            Err(_) => return CommandReturn::String("파일을 받지 못했습니다.".to_string()),
+        };
+
+        let pack = match load_quiz_pack(&raw) {
+            Ok(pack) => pack,
+            Err(why) => return CommandReturn::String(format!("퀴즈팩을 읽지 못했습니다.\n{}", why)),
+        };
+
+        let mut report = pack_report_embed(&pack);
+        // 진행할 곡이 없으면 검사 결과를 그대로 응답
+        if pack.entries.is_empty() {
+            report.footer(|f| f.text("진행할 수 있는 곡이 없습니다."));
+            return CommandReturn::SingleEmbed(report);
+        }
+        if let Err(why) = command.channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.clone_from(&report);
+                e
+            })
+        }).await {
+            println!("failed to send quiz pack report {:?}", why);
+        }
+
+        match establish_connection(ctx, command).await {
+            Ok(_) => (),
+            Err(why) => match why {
+                ConnectionErrorCode::JoinVoiceChannelFirst => return CommandReturn::String("음성채널에 먼저 접속해주세요.".to_owned()),
+                ConnectionErrorCode::AlreadyInUse => return CommandReturn::String("다른 채널에서 사용중입니다.".to_owned()),
+                _ => return CommandReturn::String("연결에 실패했습니다.".to_owned()),
+            },
+        };
+
+        let gid = command.guild_id.unwrap();
+        let mut session = QuizSession::new(gid, command.channel_id, pack.entries, pack.settings);
+        match session.run(ctx).await {
+            Some(embed) => CommandReturn::SingleEmbed(embed),
+            // 최종 순위는 채널에 올라가므로 원래 응답은 지움
+            None => CommandReturn::None,
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("퀴즈팩")
+            .description("JSON 퀴즈팩 파일로 퀴즈를 진행합니다.")
+            .create_option(|option| {
+                option
+                    .name("파일")
+                    .description("퀴즈팩 JSON 파일")
+                    .kind(CommandOptionType::Attachment)
+                    .required(true)
+            })
+    }
+}
+
+fn pack_report_embed(pack: &QuizPack) -> CreateEmbed {
+    let mut embed = CreateEmbed::default();
+    embed.title(&pack.name)
+        .description(format!(
+            "{}곡 / 정답 시간 {}초 / 힌트 간격 {}초",
+            pack.entries.len(), pack.settings.answer_time, pack.settings.hint_interval
+        ));
+    if !pack.errors.is_empty() {
+        let mut errors = pack.errors.iter()
+            .take(10)
+            .cloned()
+            .collect::<Vec<String>>();
+        if pack.errors.len() > errors.len() {
+            errors.push(format!("외 {}개", pack.errors.len() - errors.len()));
+        }
+        embed.field("건너뛴 곡", errors.join("\n"), false);
+    }
+    embed
+}
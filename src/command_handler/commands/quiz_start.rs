@@ -0,0 +1,271 @@
+use rand::Rng;
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    quiz_handler::{
+        clip::Transform,
+        game_code::{load_game_code, normalize_code},
+        quiz_session::{QuizEntry, QuizSession, QuizSettings, DEFAULT_CLIP_DURATION},
+        round::RoundKind,
+        win_condition::WinCondition,
+    },
+    database_handler::*, DBContainer,
+    connection_handler::*,
+};
+
+const DEFAULT_SONG_COUNT: i64 = 10;
+const MAX_SONG_COUNT: i64 = 50;
+const MIN_CLIP_START: u64 = 15;
+const MAX_CLIP_START: u64 = 60;
+
+struct QuizStart;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(QuizStart)
+}
+
+#[async_trait]
+impl CommandInterface for QuizStart {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let count = Option::<i64>::from(DataWrapper::from_name(options, "곡수"))
+            .unwrap_or(DEFAULT_SONG_COUNT)
+            .clamp(1, MAX_SONG_COUNT) as usize;
+        let transform = Option::<bool>::from(DataWrapper::from_name(options, "변형")).unwrap_or(false);
+        let artist = Option::<bool>::from(DataWrapper::from_name(options, "가수")).unwrap_or(false);
+        let lyrics = Option::<bool>::from(DataWrapper::from_name(options, "가사")).unwrap_or(false);
+        let lyric_audio = Option::<bool>::from(DataWrapper::from_name(options, "가사음악")).unwrap_or(false);
+        let picture = Option::<bool>::from(DataWrapper::from_name(options, "썸네일")).unwrap_or(false);
+        let hosted = Option::<bool>::from(DataWrapper::from_name(options, "호스트")).unwrap_or(false);
+        let private_answers = Option::<bool>::from(DataWrapper::from_name(options, "비공개답")).unwrap_or(false);
+        let tag_draft = Option::<bool>::from(DataWrapper::from_name(options, "태그투표")).unwrap_or(false);
+        let code = Option::<String>::from(DataWrapper::from_name(options, "code"));
+        let goal = Option::<i64>::from(DataWrapper::from_name(options, "목표"));
+        let win_condition = Option::<String>::from(DataWrapper::from_name(options, "승리조건"))
+            .and_then(|kind| WinCondition::from_option(&kind, goal))
+            .unwrap_or_default();
+
+        // 고정 라운드가 아니면 중간에 끝날 수 있으므로 곡을 넉넉히 준비
+        let count = match win_condition {
+            WinCondition::FixedRounds => count,
+            _ => MAX_SONG_COUNT as usize,
+        };
+        let settings = QuizSettings {
+            win_condition,
+            private_answers,
+            lyric_audio,
+            tag_draft,
+            ..Default::default()
+        };
+
+        let (entries, settings, dropped) = {
+            let data = ctx.data.read().await;
+            let conn = data.get::<DBContainer>().unwrap();
+            match code.as_ref() {
+                Some(code) => match load_game_code(conn, command.guild_id.unwrap().0, code.to_owned()).await {
+                    Ok(loaded) => loaded,
+                    Err(why) => return CommandReturn::String(why),
+                },
+                None => {
+                    // 가사 빈칸 퀴즈는 가사가 등록된 곡에서만 출제
+                    let songs = if lyrics {
+                        random_lyric_songs(conn, command.guild_id.unwrap().0, count).await
+                    } else {
+                        random_quiz_songs(conn, command.guild_id.unwrap().0, count).await
+                    };
+                    match songs {
+                        Ok(songs) => (random_entries(songs, transform, artist, lyrics, picture), settings, 0),
+                        Err(_) => return CommandReturn::String("곡을 불러오지 못했습니다.".to_owned()),
+                    }
+                },
+            }
+        };
+
+        if entries.is_empty() && dropped > 0 {
+            return CommandReturn::String("코드의 곡이 모두 삭제되었거나 이 서버에서 쓸 수 없는 곡입니다.".to_owned());
+        }
+        if entries.is_empty() {
+            // 공유 풀은 기본으로 꺼져 있어 처음에는 서버가 등록한 곡만 출제됨
+            // 서버별 곡 관리 이전에 등록된 곡도 공유 풀에 있으므로 함께 안내
+            return CommandReturn::String("출제할 곡이 없습니다. 이전에 등록된 곡은 공유 풀로 옮겨졌으니 /공유풀로 켜거나 /기존곡가져오기로 가져와주세요. 새 곡은 /곡추가로 등록할 수 있습니다.".to_owned());
+        }
+
+        // 호스트 진행 모드는 재생한 곡을 호스트가 듣고 채점하므로 가사, 썸네일 라운드를 진행할 수 없음
+        if hosted && entries.iter().any(|entry| matches!(entry.kind, RoundKind::Lyrics | RoundKind::Thumbnail)) {
+            return CommandReturn::String("호스트 진행 모드에서는 가사, 썸네일 라운드를 쓸 수 없습니다.".to_owned());
+        }
+
+        // 코드에 있던 곡 중 삭제되었거나 이 서버가 볼 수 없는 곡은 빼고 진행
+        if dropped > 0 {
+            if let Err(why) = command.channel_id.say(&ctx.http, format!("코드의 곡 중 {}곡은 삭제되었거나 이 서버에서 쓸 수 없어 빼고 진행합니다.", dropped)).await {
+                println!("failed to announce dropped songs {:?}", why);
+            }
+        }
+
+        match establish_connection(ctx, command).await {
+            Ok(_) => (),
+            Err(why) => match why {
+                ConnectionErrorCode::JoinVoiceChannelFirst => return CommandReturn::String("음성채널에 먼저 접속해주세요.".to_owned()),
+                ConnectionErrorCode::AlreadyInUse => return CommandReturn::String("다른 채널에서 사용중입니다.".to_owned()),
+                _ => return CommandReturn::String("연결에 실패했습니다.".to_owned()),
+            },
+        };
+
+        let gid = command.guild_id.unwrap();
+        let mut session = QuizSession::new(gid, command.channel_id, entries, settings);
+        session.code = code.map(|code| normalize_code(&code));
+        if hosted {
+            session.host = Some(command.user.id);
+        }
+        match session.run(ctx).await {
+            Some(embed) => CommandReturn::SingleEmbed(embed),
+            // 최종 순위는 채널에 올라가므로 원래 응답은 지움
+            None => CommandReturn::None,
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("퀴즈시작")
+            .description("DB에 있는 곡으로 퀴즈를 시작합니다. 게임 코드를 주면 저장된 설정을 따릅니다.")
+            .create_option(|option| {
+                option
+                    .name("곡수")
+                    .description("출제할 곡 수")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .max_int_value(MAX_SONG_COUNT)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("변형")
+                    .description("역재생, 배속 등 무작위 변형 적용")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("가수")
+                    .description("가수가 등록된 곡은 제목과 가수를 따로 맞히기")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("가사")
+                    .description("가사가 등록된 곡으로 가사 빈칸 맞히기")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("가사음악")
+                    .description("가사 빈칸 라운드에서도 노래 구간 재생")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("썸네일")
+                    .description("음악 대신 모자이크된 썸네일을 보고 맞히기")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("승리조건")
+                    .description("퀴즈 종료 조건")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("라운드 수", "rounds")
+                    .add_string_choice("N점 선취", "first_to")
+                    .add_string_choice("타임어택", "time_attack")
+                    .add_string_choice("협동", "coop")
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("목표")
+                    .description("N점 선취의 목표 점수 또는 타임어택, 협동의 제한시간(분)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .max_int_value(60)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("호스트")
+                    .description("명령어를 쓴 사람이 패널로 라운드와 채점을 직접 진행")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("비공개답")
+                    .description("채팅 대신 답 제출 버튼으로 답을 받기")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("태그투표")
+                    .description("라운드 사이에 태그 투표로 다음 곡 고르기")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("code")
+                    .description("다시 플레이할 게임 코드")
+                    .kind(CommandOptionType::String)
+                    .required(false)
+            })
+    }
+}
+
+pub fn random_entries(songs: Vec<QuizSong>, transform: bool, artist: bool, lyrics: bool, picture: bool) -> Vec<QuizEntry> {
+    let mut rng = rand::thread_rng();
+    songs.into_iter()
+        .map(|song| {
+            // 구간을 정해 둔 곡은 그 구간으로 출제
+            let (start, duration) = match song.clip.as_ref() {
+                Some(clip) => (clip.start, clip.duration.unwrap_or(DEFAULT_CLIP_DURATION)),
+                None => (rng.gen_range(MIN_CLIP_START..=MAX_CLIP_START), DEFAULT_CLIP_DURATION),
+            };
+            let transform = if transform { Transform::random() } else { Transform::Original };
+            let mut entry = QuizEntry::from_song(song, start, duration, transform);
+            if lyrics && entry.lyrics.is_some() {
+                entry.kind = RoundKind::Lyrics;
+            } else if picture {
+                entry.kind = RoundKind::Thumbnail;
+            } else if artist && !entry.artists.is_empty() {
+                entry.kind = RoundKind::TitleArtist;
+            }
+            entry
+        })
+        .collect()
+}
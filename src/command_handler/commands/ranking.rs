@@ -0,0 +1,184 @@
+use std::{
+    any::Any,
+    time::Duration,
+};
+
+use futures::StreamExt;
+use serenity::{
+    async_trait,
+    builder::{CreateApplicationCommand, CreateComponents, CreateEmbed},
+    client::{Context},
+    model::{
+        application::{
+            component::ButtonStyle,
+            interaction::{
+                application_command::ApplicationCommandInteraction,
+                InteractionResponseType,
+            },
+        },
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+use tokio::time::timeout;
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::{CommandReturn, ControlInteraction},
+        commands::song_list::truncate,
+    },
+    database_handler::*, DBContainer,
+};
+
+const RANKING_SIZE: usize = 100;
+const PLAYERS_PER_PAGE: usize = 20;
+// 이 시간 동안 아무 버튼도 누르지 않으면 버튼을 비활성화
+const RANKING_IDLE_TIMEOUT: u64 = 120;
+// 한 페이지가 discord embed 설명 제한(4096자)을 넘지 않도록 이름을 자르는 길이
+const MAX_NAME_CHARS: usize = 100;
+
+struct Ranking;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(Ranking)
+}
+
+#[async_trait]
+impl CommandInterface for Ranking {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let global = Option::<String>::from(DataWrapper::from(options, 0)).map_or(false, |scope| scope == "global");
+        let guild_id = if global { None } else { command.guild_id.map(|gid| gid.0) };
+
+        let ranking = match player_ranking(&ctx.data.read().await.get::<DBContainer>().unwrap(), guild_id, RANKING_SIZE).await {
+            Ok(ranking) => ranking,
+            Err(_) => return CommandReturn::String("오류로 인해 순위를 불러오지 못했습니다.".to_string()),
+        };
+
+        if ranking.is_empty() {
+            let mut embed = CreateEmbed::default();
+            embed.title(if global { "전체 순위" } else { "서버 순위" })
+                .description("아직 기록이 없습니다.");
+            return CommandReturn::SingleEmbed(embed);
+        }
+
+        CommandReturn::ControlInteraction(Box::new(RankingPages {
+            ranking,
+            global,
+            page: 0,
+        }))
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("랭킹")
+            .description("맞힌 곡 수 기준 상위 100명을 보여줍니다.")
+            .create_option(|option| {
+                option
+                    .name("범위")
+                    .description("서버 순위 또는 모든 서버를 합친 전체 순위")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("서버", "guild")
+                    .add_string_choice("전체", "global")
+                    .required(false)
+            })
+    }
+}
+
+struct RankingPages {
+    ranking: Vec<RankingRow>,
+    global: bool,
+    page: usize,
+}
+
+impl RankingPages {
+    fn last_page(&self) -> usize {
+        (self.ranking.len() - 1) / PLAYERS_PER_PAGE
+    }
+
+    fn page_embed(&self) -> CreateEmbed {
+        let start = self.page * PLAYERS_PER_PAGE;
+        let description = self.ranking.iter()
+            .enumerate()
+            .skip(start)
+            .take(PLAYERS_PER_PAGE)
+            .map(|(idx, row)| format!("{}. {} - {}곡", idx + 1, truncate(row.name.to_owned(), MAX_NAME_CHARS), row.correct))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let mut embed = CreateEmbed::default();
+        embed.title(format!("{} ({}/{})", if self.global { "전체 순위" } else { "서버 순위" }, self.page + 1, self.last_page() + 1))
+            .description(description);
+        embed
+    }
+
+    fn buttons(&self, disabled: bool) -> CreateComponents {
+        let first = disabled || self.page == 0;
+        let last = disabled || self.page == self.last_page();
+        let mut components = CreateComponents::default();
+        components.create_action_row(|row| {
+            row.create_button(|b| b.custom_id("ranking_first").label("≪").style(ButtonStyle::Secondary).disabled(first))
+                .create_button(|b| b.custom_id("ranking_prev").label("이전").style(ButtonStyle::Primary).disabled(first))
+                .create_button(|b| b.custom_id("ranking_next").label("다음").style(ButtonStyle::Primary).disabled(last))
+                .create_button(|b| b.custom_id("ranking_last").label("≫").style(ButtonStyle::Secondary).disabled(last))
+        });
+        components
+    }
+}
+
+#[async_trait]
+impl ControlInteraction for RankingPages {
+    async fn control_interaction(
+        &mut self,
+        ctx: &Context,
+        interaction: ApplicationCommandInteraction,
+    ) -> Result<(), serenity::Error> {
+        let msg = interaction.edit_original_interaction_response(&ctx.http, |r| {
+            r.set_embed(self.page_embed())
+                .set_components(self.buttons(false))
+        }).await?;
+
+        let mut collector = msg.await_component_interactions(ctx).build();
+        loop {
+            let component = match timeout(Duration::from_secs(RANKING_IDLE_TIMEOUT), collector.next()).await {
+                Ok(Some(component)) => component,
+                _ => break,
+            };
+            self.page = match component.data.custom_id.as_str() {
+                "ranking_first" => 0,
+                "ranking_prev" => self.page.saturating_sub(1),
+                "ranking_next" => (self.page + 1).min(self.last_page()),
+                "ranking_last" => self.last_page(),
+                _ => self.page,
+            };
+            component.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| {
+                        d.set_embed(self.page_embed())
+                            .set_components(self.buttons(false))
+                    })
+            }).await?;
+        }
+
+        interaction.edit_original_interaction_response(&ctx.http, |r| {
+            r.set_components(self.buttons(true))
+        }).await?;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
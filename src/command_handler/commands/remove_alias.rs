@@ -0,0 +1,90 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction,
+            autocomplete::AutocompleteInteraction,
+        },
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::{
+        audio_module::youtube_dl::remove_cache,
+        image_module::thumbnail::remove_thumbnail,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct RemoveAlias;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(RemoveAlias)
+}
+
+#[async_trait]
+impl CommandInterface for RemoveAlias {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let title = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(title) => title,
+            None => return CommandReturn::String("제목을 입력해주세요.".to_string()),
+        };
+
+        match remove_title(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, title.to_owned()).await {
+            Ok(None) => CommandReturn::String(format!("{}을 삭제했습니다.", title)),
+            // 마지막 제목이었으면 곡도 같이 지워짐
+            Ok(Some(url)) => {
+                remove_cache(&url).await;
+                remove_thumbnail(&url).await;
+                CommandReturn::String(format!("{}을 삭제했습니다. 남은 제목이 없어 곡도 삭제되었습니다.", title))
+            },
+            Err(why) => match why {
+                DBError::TitleNotFound => CommandReturn::String("DB에 없는 제목입니다.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡의 제목만 삭제할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 별칭이 삭제되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("별칭삭제")
+            .description("곡의 제목이나 별칭 하나를 삭제합니다.")
+            .create_option(|option| {
+                option
+                    .name("제목")
+                    .description("삭제할 제목")
+                    .kind(CommandOptionType::String)
+                    .set_autocomplete(true)
+                    .required(true)
+            })
+    }
+
+    async fn autocomplete(
+        &self,
+        ctx: &Context,
+        autocomplete: &AutocompleteInteraction
+    ) {
+        autocomplete_titles(ctx, autocomplete).await;
+    }
+}
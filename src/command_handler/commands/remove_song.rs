@@ -0,0 +1,88 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction,
+            autocomplete::AutocompleteInteraction,
+        },
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::{
+        audio_module::youtube_dl::remove_cache,
+        image_module::thumbnail::remove_thumbnail,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct RemoveSong;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(RemoveSong)
+}
+
+#[async_trait]
+impl CommandInterface for RemoveSong {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let title = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(title) => title,
+            None => return CommandReturn::String("제목을 입력해주세요.".to_string()),
+        };
+
+        match remove_song(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, title.to_owned()).await {
+            Ok((url, titles)) => {
+                remove_cache(&url).await;
+                remove_thumbnail(&url).await;
+                CommandReturn::String(format!("곡을 삭제했습니다. (제목 {}개: {})", titles.len(), titles.join(", ")))
+            },
+            Err(why) => match why {
+                DBError::TitleNotFound => CommandReturn::String("DB에 없는 제목입니다.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡만 삭제할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 곡이 삭제되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("곡삭제")
+            .description("곡과 곡의 모든 제목, 받아둔 파일을 삭제합니다.")
+            .create_option(|option| {
+                option
+                    .name("제목")
+                    .description("삭제할 곡의 제목")
+                    .kind(CommandOptionType::String)
+                    .set_autocomplete(true)
+                    .required(true)
+            })
+    }
+
+    async fn autocomplete(
+        &self,
+        ctx: &Context,
+        autocomplete: &AutocompleteInteraction
+    ) {
+        autocomplete_titles(ctx, autocomplete).await;
+    }
+}
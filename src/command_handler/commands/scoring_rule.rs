@@ -0,0 +1,155 @@
+use serenity::{
+    async_trait,
+    builder::{CreateApplicationCommand, CreateEmbed},
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+        Permissions,
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    quiz_handler::scoring::{load_scoring_rules, save_scoring_rules, ScoringRules},
+    DBContainer,
+};
+
+struct ScoringRule;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(ScoringRule)
+}
+
+#[async_trait]
+impl CommandInterface for ScoringRule {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let gid = command.guild_id.unwrap();
+        let data = ctx.data.read().await;
+        let conn = data.get::<DBContainer>().unwrap();
+
+        // 준 옵션만 바꾸고 나머지는 유지, 옵션이 없으면 현재 규칙만 보여줌
+        let reset = Option::<bool>::from(DataWrapper::from_name(options, "초기화")).unwrap_or(false);
+        let mut rules = if reset {
+            ScoringRules::default()
+        } else {
+            load_scoring_rules(conn, gid.0).await
+        };
+        if let Some(value) = Option::<i64>::from(DataWrapper::from_name(options, "정답점수")) {
+            rules.correct_points = value;
+        }
+        if let Some(value) = Option::<i64>::from(DataWrapper::from_name(options, "속도보너스")) {
+            rules.speed_bonus = value;
+        }
+        if let Some(value) = Option::<i64>::from(DataWrapper::from_name(options, "속도시간")) {
+            rules.speed_window = value as u64;
+        }
+        if let Some(value) = Option::<i64>::from(DataWrapper::from_name(options, "연속보너스")) {
+            rules.streak_bonus = value;
+        }
+        if let Some(value) = Option::<i64>::from(DataWrapper::from_name(options, "연속최대")) {
+            rules.streak_cap = value;
+        }
+        if let Some(value) = Option::<i64>::from(DataWrapper::from_name(options, "오답감점")) {
+            rules.wrong_penalty = value;
+        }
+
+        let title = if options.is_empty() {
+            "현재 점수 규칙"
+        } else {
+            if save_scoring_rules(conn, gid.0, &rules).await.is_err() {
+                return CommandReturn::String("오류로 인해 점수 규칙이 저장되지 않았습니다.".to_string());
+            }
+            "점수 규칙을 저장했습니다."
+        };
+
+        let mut embed = CreateEmbed::default();
+        embed.title(title)
+            .description(rules.description());
+        CommandReturn::SingleEmbed(embed)
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("점수규칙")
+            .description("이 서버의 퀴즈 점수 규칙을 보거나 바꿉니다.")
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .create_option(|option| {
+                option
+                    .name("정답점수")
+                    .description("정답 하나당 점수")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .max_int_value(100)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("속도보너스")
+                    .description("재생 직후 맞혔을 때의 최대 보너스 (시간이 지날수록 줄어듦)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(0)
+                    .max_int_value(100)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("속도시간")
+                    .description("속도 보너스가 0이 되는 시간(초)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .max_int_value(120)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("연속보너스")
+                    .description("연속 정답마다 늘어나는 배율(%)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(0)
+                    .max_int_value(100)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("연속최대")
+                    .description("연속 정답 배율의 최대치(%)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(0)
+                    .max_int_value(500)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("오답감점")
+                    .description("틀린 답 하나당 감점 (협동 모드는 팀 점수에서도 감점)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(0)
+                    .max_int_value(100)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("초기화")
+                    .description("기본 규칙으로 되돌린 뒤 적용")
+                    .kind(CommandOptionType::Boolean)
+                    .required(false)
+            })
+    }
+}
@@ -0,0 +1,77 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    quiz_handler::season::{current_season, season_embed, SEASON_RANKING_SIZE},
+    database_handler::*, DBContainer,
+};
+
+struct Season;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(Season)
+}
+
+#[async_trait]
+impl CommandInterface for Season {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let gid = command.guild_id.unwrap();
+        let current = current_season();
+        let season = Option::<String>::from(DataWrapper::from(options, 0))
+            .map(|season| season.trim().to_owned())
+            .unwrap_or_else(|| current.to_owned());
+
+        let data = ctx.data.read().await;
+        let conn = data.get::<DBContainer>().unwrap();
+        let ranking = match season_ranking(conn, season.to_owned(), gid.0, SEASON_RANKING_SIZE).await {
+            Ok(ranking) => ranking,
+            Err(_) => return CommandReturn::String("오류로 인해 시즌 순위를 불러오지 못했습니다.".to_string()),
+        };
+
+        let mut embed = season_embed(&season, &ranking, season != current);
+        // 지난 시즌 목록도 같이 보여줌
+        if let Ok(seasons) = archived_seasons(conn, gid.0).await {
+            if !seasons.is_empty() {
+                embed.field("지난 시즌", seasons.iter().take(12).map(String::as_str).collect::<Vec<&str>>().join(", "), false);
+            }
+        }
+        CommandReturn::SingleEmbed(embed)
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("시즌")
+            .description("이 서버의 시즌 순위를 보여줍니다. 시즌은 한국 시간 기준 매달 바뀝니다.")
+            .create_option(|option| {
+                option
+                    .name("시즌")
+                    .description("볼 시즌 (예: 2024-01, 기본값: 현재 시즌)")
+                    .kind(CommandOptionType::String)
+                    .required(false)
+            })
+    }
+}
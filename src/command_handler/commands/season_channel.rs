@@ -0,0 +1,67 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        channel::{ChannelType, PartialChannel},
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct SeasonChannel;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(SeasonChannel)
+}
+
+#[async_trait]
+impl CommandInterface for SeasonChannel {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let channel = match Option::<PartialChannel>::from(DataWrapper::from(options, 0)) {
+            Some(channel) => channel,
+            None => return CommandReturn::String("채널을 선택해주세요.".to_string()),
+        };
+
+        let gid = command.guild_id.unwrap();
+        match set_season_channel(&ctx.data.read().await.get::<DBContainer>().unwrap(), gid.0, channel.id.0).await {
+            Ok(_) => CommandReturn::String(format!("시즌이 끝나면 <#{}>에 최종 순위를 올립니다.", channel.id.0)),
+            Err(why) => CommandReturn::String(format!("오류로 인해 채널이 설정되지 않았습니다. ({})", why)),
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("시즌채널")
+            .description("시즌 최종 순위를 올릴 채널을 설정합니다.")
+            .create_option(|option| {
+                option
+                    .name("채널")
+                    .description("순위를 올릴 텍스트 채널")
+                    .kind(CommandOptionType::Channel)
+                    .channel_types(&[ChannelType::Text])
+                    .required(true)
+            })
+    }
+}
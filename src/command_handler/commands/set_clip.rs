@@ -0,0 +1,102 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::{
+        url_checker::{url_checker},
+        song_file::{MAX_CLIP_START, MAX_CLIP_DURATION},
+    },
+    database_handler::*, DBContainer,
+};
+
+struct SetClip;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(SetClip)
+}
+
+#[async_trait]
+impl CommandInterface for SetClip {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let url = match Option::<String>::from(DataWrapper::from_name(options, "url")) {
+            Some(url) => url,
+            None => return CommandReturn::String("url을 입력해주세요.".to_string()),
+        };
+
+        let start = match Option::<i64>::from(DataWrapper::from_name(options, "시작")) {
+            Some(start) => start as u64,
+            None => return CommandReturn::String("시작 시간을 입력해주세요.".to_string()),
+        };
+        // 길이를 안 주면 기본 길이로 출제
+        let duration = Option::<i64>::from(DataWrapper::from_name(options, "길이")).map(|duration| duration as u64);
+
+        let url = match url_checker(&url) {
+            Some(url) => url,
+            None => return CommandReturn::String("유효한 url이 아닙니다.".to_string()),
+        };
+
+        match set_song_clip(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, url, start, duration).await {
+            Ok(_) => CommandReturn::String(format!("출제 구간을 {}초부터로 설정했습니다.", start)),
+            Err(why) => match why {
+                DBError::UrlNotFound => CommandReturn::String("DB에 없는 곡입니다. 먼저 곡을 추가해주세요.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡의 구간만 설정할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 구간이 설정되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("구간설정")
+            .description("퀴즈에서 곡을 무작위 구간 대신 정한 구간으로 출제합니다.")
+            .create_option(|option| {
+                option
+                    .name("url")
+                    .description("구간을 설정할 노래 url")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("시작")
+                    .description("구간 시작(초)")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(0)
+                    .max_int_value(MAX_CLIP_START)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("길이")
+                    .description("구간 길이(초), 비우면 기본 길이")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1)
+                    .max_int_value(MAX_CLIP_DURATION)
+                    .required(false)
+            })
+    }
+}
@@ -0,0 +1,117 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        channel::Attachment,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    quiz_handler::lyric::make_blank,
+    utils::url_checker::{url_checker},
+    database_handler::*, DBContainer,
+};
+
+const MAX_LYRICS_SIZE: u64 = 64 * 1024;
+
+struct SetLyrics;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(SetLyrics)
+}
+
+#[async_trait]
+impl CommandInterface for SetLyrics {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let url = match Option::<String>::from(DataWrapper::from_name(options, "url")) {
+            Some(url) => url,
+            None => return CommandReturn::String("url을 입력해주세요.".to_string()),
+        };
+
+        let url = match url_checker(&url) {
+            Some(url) => url,
+            None => return CommandReturn::String("유효한 url이 아닙니다.".to_string()),
+        };
+
+        // 슬래시 명령어 입력창은 줄바꿈이 안 되므로 직접 입력할 때는 / 로 줄을 구분
+        let lyrics = match Option::<Attachment>::from(DataWrapper::from_name(options, "파일")) {
+            Some(attachment) => {
+                if attachment.size > MAX_LYRICS_SIZE {
+                    return CommandReturn::String("파일이 너무 큽니다.".to_string());
+                }
+                match attachment.download().await {
+                    Ok(raw) => match String::from_utf8(raw) {
+                        Ok(lyrics) => lyrics,
+                        Err(_) => return CommandReturn::String("UTF-8 텍스트 파일만 사용할 수 있습니다.".to_string()),
+                    },
+                    Err(_) => return CommandReturn::String("파일을 받지 못했습니다.".to_string()),
+                }
+            },
+            None => match Option::<String>::from(DataWrapper::from_name(options, "가사")) {
+                Some(lyrics) => lyrics.split('/').map(str::trim).collect::<Vec<&str>>().join("\n"),
+                None => return CommandReturn::String("가사나 가사 파일을 입력해주세요.".to_string()),
+            },
+        };
+
+        if make_blank(&lyrics).is_none() {
+            return CommandReturn::String("빈칸을 만들 수 있는 가사 줄이 없습니다. 세 단어 이상인 줄이 필요합니다.".to_string());
+        }
+
+        let lines = lyrics.lines().filter(|line| !line.trim().is_empty()).count();
+        match set_lyrics(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, url, lyrics).await {
+            Ok(_) => CommandReturn::String(format!("가사 {}줄을 저장했습니다.", lines)),
+            Err(why) => match why {
+                DBError::UrlNotFound => CommandReturn::String("DB에 없는 곡입니다. 먼저 곡을 추가해주세요.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡에만 가사를 설정할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 가사가 저장되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("가사설정")
+            .description("DB에 있는 곡의 가사를 등록합니다. 가사 빈칸 퀴즈에 사용됩니다.")
+            .create_option(|option| {
+                option
+                    .name("url")
+                    .description("가사를 등록할 노래 url")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("가사")
+                    .description("가사 (줄은 / 로 구분)")
+                    .kind(CommandOptionType::String)
+                    .required(false)
+            })
+            .create_option(|option| {
+                option
+                    .name("파일")
+                    .description("가사 텍스트 파일 (가사 옵션보다 우선)")
+                    .kind(CommandOptionType::Attachment)
+                    .required(false)
+            })
+    }
+}
@@ -0,0 +1,109 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        channel::Attachment,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::{
+        url_checker::{url_checker},
+        lrc::parse_lrc,
+    },
+    database_handler::*, DBContainer,
+};
+
+const MAX_LRC_SIZE: u64 = 64 * 1024;
+
+struct SetSyncedLyrics;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(SetSyncedLyrics)
+}
+
+#[async_trait]
+impl CommandInterface for SetSyncedLyrics {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let url = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(url) => url,
+            None => return CommandReturn::String("url을 입력해주세요.".to_string()),
+        };
+
+        let attachment = match Option::<Attachment>::from(DataWrapper::from(options, 1)) {
+            Some(attachment) => attachment,
+            None => return CommandReturn::String("LRC 파일을 첨부해주세요.".to_string()),
+        };
+
+        let url = match url_checker(&url) {
+            Some(url) => url,
+            None => return CommandReturn::String("유효한 url이 아닙니다.".to_string()),
+        };
+
+        if attachment.size > MAX_LRC_SIZE {
+            return CommandReturn::String("파일이 너무 큽니다.".to_string());
+        }
+
+        let lrc = match attachment.download().await {
+            Ok(raw) => match String::from_utf8(raw) {
+                Ok(lrc) => lrc,
+                Err(_) => return CommandReturn::String("UTF-8 텍스트 파일만 사용할 수 있습니다.".to_string()),
+            },
+            Err(_) => return CommandReturn::String("파일을 받지 못했습니다.".to_string()),
+        };
+
+        let lines = parse_lrc(&lrc).len();
+        if lines == 0 {
+            return CommandReturn::String("시간 태그가 있는 가사 줄이 없습니다.".to_string());
+        }
+
+        match set_synced_lyrics(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, url, lrc).await {
+            Ok(_) => CommandReturn::String(format!("싱크 가사 {}줄을 저장했습니다.", lines)),
+            Err(why) => match why {
+                DBError::UrlNotFound => CommandReturn::String("DB에 없는 곡입니다. 먼저 곡을 추가해주세요.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡에만 싱크 가사를 설정할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 싱크 가사가 저장되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("싱크가사")
+            .description("DB에 있는 곡에 LRC 가사를 등록합니다. 재생 중 가사가 표시됩니다.")
+            .create_option(|option| {
+                option
+                    .name("url")
+                    .description("가사를 등록할 노래 url")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("파일")
+                    .description("LRC 파일")
+                    .kind(CommandOptionType::Attachment)
+                    .required(true)
+            })
+    }
+}
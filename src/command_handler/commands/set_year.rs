@@ -0,0 +1,88 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    utils::url_checker::{url_checker},
+    database_handler::*, DBContainer,
+};
+
+struct SetYear;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(SetYear)
+}
+
+#[async_trait]
+impl CommandInterface for SetYear {
+    async fn run(
+        &self, 
+        ctx: &Context, 
+        command: &ApplicationCommandInteraction, 
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let url = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(url) => url,
+            None => return CommandReturn::String("url을 입력해주세요.".to_string()),
+        };
+
+        let year = match Option::<i64>::from(DataWrapper::from(options, 1)) {
+            Some(year) => year,
+            None => return CommandReturn::String("연도를 입력해주세요.".to_string()),
+        };
+
+        let url = match url_checker(&url) {
+            Some(url) => url,
+            None => return CommandReturn::String("유효한 url이 아닙니다.".to_string()),
+        };
+
+        match set_release_year(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, url, year).await {
+            Ok(_) => CommandReturn::String(format!("발매연도를 {}년으로 설정했습니다.", year)),
+            Err(why) => match why {
+                DBError::UrlNotFound => CommandReturn::String("DB에 없는 곡입니다. 먼저 곡을 추가해주세요.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡의 발매연도만 설정할 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 발매연도가 설정되지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("연도설정")
+            .description("DB에 있는 곡의 발매연도를 설정합니다.")
+            .create_option(|option| {
+                option
+                    .name("url")
+                    .description("발매연도를 설정할 노래 url")
+                    .kind(CommandOptionType::String)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("연도")
+                    .description("발매연도")
+                    .kind(CommandOptionType::Integer)
+                    .min_int_value(1900)
+                    .max_int_value(2100)
+                    .required(true)
+            })
+    }
+}
@@ -0,0 +1,95 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction,
+            autocomplete::AutocompleteInteraction,
+        },
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+        Permissions,
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct ShareSong;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(ShareSong)
+}
+
+#[async_trait]
+impl CommandInterface for ShareSong {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let title = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(title) => title,
+            None => return CommandReturn::String("제목을 입력해주세요.".to_string()),
+        };
+        let shared = match Option::<bool>::from(DataWrapper::from(options, 1)) {
+            Some(shared) => shared,
+            None => return CommandReturn::String("공유 여부를 선택해주세요.".to_string()),
+        };
+
+        let gid = command.guild_id.unwrap();
+        match set_song_shared(&ctx.data.read().await.get::<DBContainer>().unwrap(), gid.0, title.to_owned(), shared).await {
+            Ok(_) if shared => CommandReturn::String(format!("{}을 공유 풀에 공개했습니다.", title)),
+            Ok(_) => CommandReturn::String(format!("{}을 공유 풀에서 내렸습니다. 이제 이 서버에서만 출제됩니다.", title)),
+            Err(why) => match why {
+                DBError::TitleNotFound => CommandReturn::String("DB에 없는 제목입니다.".to_string()),
+                DBError::NotOwner => CommandReturn::String("이 서버가 등록한 곡만 공유 설정을 바꿀 수 있습니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 공유 설정이 바뀌지 않았습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("곡공유")
+            .description("서버가 등록한 곡을 다른 서버도 쓸 수 있는 공유 풀에 공개하거나 내립니다.")
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .create_option(|option| {
+                option
+                    .name("제목")
+                    .description("공유 설정을 바꿀 곡의 제목")
+                    .kind(CommandOptionType::String)
+                    .set_autocomplete(true)
+                    .required(true)
+            })
+            .create_option(|option| {
+                option
+                    .name("공유")
+                    .description("공유 풀에 공개하려면 True, 내리려면 False")
+                    .kind(CommandOptionType::Boolean)
+                    .required(true)
+            })
+    }
+
+    async fn autocomplete(
+        &self,
+        ctx: &Context,
+        autocomplete: &AutocompleteInteraction
+    ) {
+        autocomplete_titles(ctx, autocomplete).await;
+    }
+}
@@ -0,0 +1,68 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::application_command::ApplicationCommandInteraction,
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+        Permissions,
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct SharedPool;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(SharedPool)
+}
+
+#[async_trait]
+impl CommandInterface for SharedPool {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let use_shared = match Option::<bool>::from(DataWrapper::from(options, 0)) {
+            Some(use_shared) => use_shared,
+            None => return CommandReturn::String("사용 여부를 선택해주세요.".to_string()),
+        };
+
+        let gid = command.guild_id.unwrap();
+        match set_use_shared(&ctx.data.read().await.get::<DBContainer>().unwrap(), gid.0, use_shared).await {
+            Ok(_) if use_shared => CommandReturn::String("이제 퀴즈에 공유 풀의 곡도 출제됩니다.".to_string()),
+            Ok(_) => CommandReturn::String("이제 퀴즈에 이 서버가 등록한 곡만 출제됩니다.".to_string()),
+            Err(why) => CommandReturn::String(format!("오류로 인해 설정이 바뀌지 않았습니다. ({})", why)),
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("공유풀")
+            .description("다른 서버가 공개한 곡과 기존 공용 곡을 이 서버의 퀴즈에 쓸지 정합니다. (기본 끔)")
+            .default_member_permissions(Permissions::MANAGE_GUILD)
+            .create_option(|option| {
+                option
+                    .name("사용")
+                    .description("공유 풀을 쓰려면 True")
+                    .kind(CommandOptionType::Boolean)
+                    .required(true)
+            })
+    }
+}
@@ -0,0 +1,202 @@
+use std::{
+    any::Any,
+    time::Duration,
+};
+
+use futures::StreamExt;
+use serenity::{
+    async_trait,
+    builder::{CreateApplicationCommand, CreateComponents, CreateEmbed},
+    client::{Context},
+    model::{
+        application::{
+            component::ButtonStyle,
+            interaction::{
+                application_command::ApplicationCommandInteraction,
+                InteractionResponseType,
+            },
+        },
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+use tokio::time::timeout;
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::{CommandReturn, ControlInteraction},
+    },
+    database_handler::*, DBContainer,
+};
+
+const SONGS_PER_PAGE: usize = 5;
+// 이 시간 동안 아무 버튼도 누르지 않으면 버튼을 비활성화
+const LIBRARY_IDLE_TIMEOUT: u64 = 120;
+// discord embed 제한(필드 이름 256자, 값 1024자, 전체 6000자)을 넘지 않도록 자르는 길이
+// 한 페이지 필드 5개가 모두 최대여도 6000자 안에 들어감
+const MAX_NAME_CHARS: usize = 100;
+const MAX_LIST_CHARS: usize = 400;
+
+struct SongList;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(SongList)
+}
+
+#[async_trait]
+impl CommandInterface for SongList {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let filter = Option::<String>::from(DataWrapper::from_name(options, "검색"));
+
+        let songs = match library_songs(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, filter.to_owned()).await {
+            Ok(songs) => songs,
+            Err(why) => return CommandReturn::String(format!("오류로 인해 곡 목록을 불러오지 못했습니다. ({})", why)),
+        };
+        if songs.is_empty() {
+            return match filter {
+                Some(filter) => CommandReturn::String(format!("'{}'에 해당하는 곡이 없습니다.", filter)),
+                // 서버별 곡 관리 이전에 등록된 곡은 공유 풀로 옮겨져 공유 풀을 켜야 보임
+                None => CommandReturn::String("이 서버에서 쓸 수 있는 곡이 없습니다. 이전에 등록된 곡은 공유 풀로 옮겨졌으니 /공유풀로 켜거나 /기존곡가져오기로 가져와주세요.".to_string()),
+            };
+        }
+
+        CommandReturn::ControlInteraction(Box::new(SongLibrary {
+            songs,
+            filter,
+            page: 0,
+        }))
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("곡목록")
+            .description("DB에 등록된 곡을 페이지로 넘겨봅니다.")
+            .create_option(|option| {
+                option
+                    .name("검색")
+                    .description("제목이나 태그에 이 단어가 들어간 곡만 보기")
+                    .kind(CommandOptionType::String)
+                    .required(false)
+            })
+    }
+}
+
+struct SongLibrary {
+    songs: Vec<LibrarySong>,
+    filter: Option<String>,
+    page: usize,
+}
+
+impl SongLibrary {
+    fn last_page(&self) -> usize {
+        (self.songs.len() - 1) / SONGS_PER_PAGE
+    }
+
+    fn page_embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        embed.title(format!("곡 목록 ({}/{})", self.page + 1, self.last_page() + 1));
+        match self.filter.as_ref() {
+            Some(filter) => embed.description(format!("'{}' 검색 결과 {}곡", truncate(filter.to_owned(), MAX_NAME_CHARS), self.songs.len())),
+            None => embed.description(format!("전체 {}곡", self.songs.len())),
+        };
+
+        let start = self.page * SONGS_PER_PAGE;
+        for (idx, song) in self.songs.iter().enumerate().skip(start).take(SONGS_PER_PAGE) {
+            let name = song.titles.first().map_or("(제목 없음)", |title| title.as_str());
+            let mut lines = vec![format!("https://youtu.be/{}", song.url)];
+            if song.titles.len() > 1 {
+                lines.push(truncate(format!("별칭: {}", song.titles[1..].join(", ")), MAX_LIST_CHARS));
+            }
+            if !song.tags.is_empty() {
+                lines.push(truncate(format!("태그: {}", song.tags.join(", ")), MAX_LIST_CHARS));
+            }
+            // 메타데이터를 아직 받지 못한 곡은 길이를 모름
+            if let Some(duration) = song.duration {
+                let secs = duration.as_secs();
+                lines.push(format!("길이: {}:{:02}", secs / 60, secs % 60));
+            }
+            embed.field(truncate(format!("{}. {}", idx + 1, name), MAX_NAME_CHARS), lines.join("\n"), false);
+        }
+        embed
+    }
+
+    fn buttons(&self, disabled: bool) -> CreateComponents {
+        let first = disabled || self.page == 0;
+        let last = disabled || self.page == self.last_page();
+        let mut components = CreateComponents::default();
+        components.create_action_row(|row| {
+            row.create_button(|b| b.custom_id("library_first").label("≪").style(ButtonStyle::Secondary).disabled(first))
+                .create_button(|b| b.custom_id("library_prev").label("이전").style(ButtonStyle::Primary).disabled(first))
+                .create_button(|b| b.custom_id("library_next").label("다음").style(ButtonStyle::Primary).disabled(last))
+                .create_button(|b| b.custom_id("library_last").label("≫").style(ButtonStyle::Secondary).disabled(last))
+        });
+        components
+    }
+}
+
+pub(crate) fn truncate(text: String, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text;
+    }
+    let mut truncated = text.chars().take(max - 1).collect::<String>();
+    truncated.push('…');
+    truncated
+}
+
+#[async_trait]
+impl ControlInteraction for SongLibrary {
+    async fn control_interaction(
+        &mut self,
+        ctx: &Context,
+        interaction: ApplicationCommandInteraction,
+    ) -> Result<(), serenity::Error> {
+        let msg = interaction.edit_original_interaction_response(&ctx.http, |r| {
+            r.set_embed(self.page_embed())
+                .set_components(self.buttons(false))
+        }).await?;
+
+        let mut collector = msg.await_component_interactions(ctx).build();
+        loop {
+            let component = match timeout(Duration::from_secs(LIBRARY_IDLE_TIMEOUT), collector.next()).await {
+                Ok(Some(component)) => component,
+                _ => break,
+            };
+            self.page = match component.data.custom_id.as_str() {
+                "library_first" => 0,
+                "library_prev" => self.page.saturating_sub(1),
+                "library_next" => (self.page + 1).min(self.last_page()),
+                "library_last" => self.last_page(),
+                _ => self.page,
+            };
+            component.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| {
+                        d.set_embed(self.page_embed())
+                            .set_components(self.buttons(false))
+                    })
+            }).await?;
+        }
+
+        interaction.edit_original_interaction_response(&ctx.http, |r| {
+            r.set_components(self.buttons(true))
+        }).await?;
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
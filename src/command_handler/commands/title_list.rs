@@ -0,0 +1,87 @@
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    client::{Context},
+    model::{
+        application::interaction::{
+            application_command::ApplicationCommandInteraction,
+            autocomplete::AutocompleteInteraction,
+        },
+        prelude::{
+            interaction::application_command::{CommandDataOption},
+            command::CommandOptionType,
+        },
+    },
+};
+
+use crate::{
+    command_handler::{
+        command_handler::*,
+        command_data::*,
+        command_return::CommandReturn,
+    },
+    database_handler::*, DBContainer,
+};
+
+struct TitleList;
+
+pub fn command() -> Box<dyn CommandInterface + Sync + Send> {
+    Box::new(TitleList)
+}
+
+#[async_trait]
+impl CommandInterface for TitleList {
+    async fn run(
+        &self,
+        ctx: &Context,
+        command: &ApplicationCommandInteraction,
+        options: &[CommandDataOption]
+    ) -> CommandReturn {
+
+        let title = match Option::<String>::from(DataWrapper::from(options, 0)) {
+            Some(title) => title,
+            None => return CommandReturn::String("제목을 입력해주세요.".to_string()),
+        };
+
+        match titles_of_song(&ctx.data.read().await.get::<DBContainer>().unwrap(), command.guild_id.unwrap().0, title.to_owned()).await {
+            Ok((url, titles)) => CommandReturn::String(format!(
+                "https://youtu.be/{}\n{}",
+                url,
+                titles.iter()
+                    .enumerate()
+                    .map(|(idx, title)| format!("{}. {}", idx + 1, title))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            )),
+            Err(why) => match why {
+                DBError::TitleNotFound => CommandReturn::String("DB에 없는 제목입니다.".to_string()),
+                why => CommandReturn::String(format!("오류로 인해 제목을 불러오지 못했습니다. ({})", why)),
+            },
+        }
+    }
+
+    fn register<'a: 'b, 'b>(
+        &'a self,
+        command: &'a mut CreateApplicationCommand
+    ) -> &'b mut CreateApplicationCommand {
+        command
+            .name("제목목록")
+            .description("곡에 등록된 제목과 별칭을 모두 보여줍니다.")
+            .create_option(|option| {
+                option
+                    .name("제목")
+                    .description("찾을 곡의 제목")
+                    .kind(CommandOptionType::String)
+                    .set_autocomplete(true)
+                    .required(true)
+            })
+    }
+
+    async fn autocomplete(
+        &self,
+        ctx: &Context,
+        autocomplete: &AutocompleteInteraction
+    ) {
+        autocomplete_titles(ctx, autocomplete).await;
+    }
+}
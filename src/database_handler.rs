@@ -1,16 +1,202 @@
 use rusqlite::ffi::Error;
 use serde::Serialize;
 use tokio_rusqlite::Connection as Connection;
-use rusqlite::{Result, params, ErrorCode};
+use rusqlite::{Result, params, ErrorCode, OptionalExtension};
 use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc, Weekday, Date, NaiveDateTime, Duration};
 use chrono_tz::Asia::Seoul;
 use chrono_tz::Tz;
 use log::{error, info, warn};
 
+use crate::{
+    database_migration::migrate,
+    utils::{
+        audio_module::metadata::{Metadata, SAMPLE_RATE},
+        song_file::SongRow,
+        url_checker::YOUTUBE_PREFIX,
+    },
+};
+
+#[derive(Debug)]
 pub enum DBError {
+    // 아래로 분류되지 않은 sqlite 오류
     LibError(rusqlite::ErrorCode),
+    // 찾는 행이 없음
+    NotFound,
+    // UNIQUE, FOREIGN KEY 등 제약 조건 위반
+    ConstraintViolation,
+    // 다른 연결이 DB를 잠그고 있음
+    Busy,
     TitleAlreadyUsed,
     TitleNotFound,
+    UrlNotFound,
+    CodeAlreadyUsed,
+    CodeNotFound,
+    // 이 서버가 소유하지 않은 곡 (다른 서버 곡이나 /기존곡가져오기로 가져가지 않은 예전 곡)
+    NotOwner,
+    PlayerNotFound,
+    RuleNotFound,
+    ChallengeNotFound,
+    ChallengeAlreadyPlayed,
+}
+
+impl From<rusqlite::Error> for DBError {
+    fn from(why: rusqlite::Error) -> Self {
+        match why {
+            rusqlite::Error::QueryReturnedNoRows => DBError::NotFound,
+            rusqlite::Error::SqliteFailure(error, _) => match error.code {
+                ErrorCode::ConstraintViolation => DBError::ConstraintViolation,
+                ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked => DBError::Busy,
+                code => DBError::LibError(code),
+            },
+            _ => DBError::LibError(ErrorCode::Unknown),
+        }
+    }
+}
+
+// 찾는 행이 없을 때만 not_found로 바꾸고 나머지 오류는 그대로 전달
+fn or_not_found(why: rusqlite::Error, not_found: DBError) -> DBError {
+    match why {
+        rusqlite::Error::QueryReturnedNoRows => not_found,
+        why => why.into(),
+    }
+}
+
+// 명령어에서 그대로 보여줄 수 있는 설명
+impl std::fmt::Display for DBError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            DBError::LibError(_) => "DB 오류가 발생했습니다.",
+            DBError::NotFound => "DB에서 찾을 수 없습니다.",
+            DBError::ConstraintViolation => "이미 등록되어 있거나 올바르지 않은 값입니다.",
+            DBError::Busy => "DB가 사용 중입니다. 잠시 후 다시 시도해주세요.",
+            DBError::TitleAlreadyUsed => "이미 사용 중인 제목입니다.",
+            DBError::TitleNotFound => "DB에 없는 제목입니다.",
+            DBError::UrlNotFound => "DB에 없는 곡입니다.",
+            DBError::CodeAlreadyUsed => "이미 사용 중인 코드입니다.",
+            DBError::CodeNotFound => "존재하지 않는 코드입니다.",
+            DBError::NotOwner => "이 서버가 등록한 곡이 아닙니다.",
+            DBError::PlayerNotFound => "퀴즈 기록이 없습니다.",
+            DBError::RuleNotFound => "설정된 점수 규칙이 없습니다.",
+            DBError::ChallengeNotFound => "존재하지 않는 도전입니다.",
+            DBError::ChallengeAlreadyPlayed => "이미 플레이한 도전입니다.",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+pub struct DatedSong {
+    pub url: String,
+    pub title: String,
+    pub year: i64,
+}
+
+pub struct QuizSong {
+    pub id: u64,
+    pub url: String,
+    pub titles: Vec<String>,
+    pub artists: Vec<String>,
+    pub lyrics: Option<String>,
+    // /구간설정으로 정해 둔 출제 구간
+    pub clip: Option<SongClip>,
+}
+
+pub struct SongClip {
+    pub start: u64,
+    pub duration: Option<u64>,
+}
+
+// 퀴즈 한 판에서 쌓인 개인 기록
+pub struct PlayerRecord {
+    pub user_id: u64,
+    pub name: String,
+    // 답을 낸 라운드 수
+    pub rounds: i64,
+    pub correct: i64,
+    // 맞힐 때까지 걸린 시간의 합(ms)
+    pub reaction_ms: i64,
+    pub best_streak: i64,
+    // 맞힌 곡의 url id (선호 태그 집계용)
+    pub solved_urls: Vec<u64>,
+    // 가장 빨리 맞힌 시간(ms)
+    pub fastest_ms: Option<i64>,
+    // 이번 판 1등 여부
+    pub won: bool,
+}
+
+// 업적 판정에 쓰는 누적 기록
+pub struct AchievementProgress {
+    pub correct: i64,
+    pub best_streak: i64,
+    pub fastest_ms: Option<i64>,
+    pub wins: i64,
+}
+
+pub struct PlayerProfile {
+    pub name: String,
+    pub rounds: i64,
+    pub correct: i64,
+    pub reaction_ms: i64,
+    pub best_streak: i64,
+    // 맞힌 횟수가 많은 태그 순
+    pub tags: Vec<(String, i64)>,
+    pub rank: i64,
+}
+
+pub struct RankingRow {
+    pub name: String,
+    pub correct: i64,
+}
+
+// 시즌이 끝난 서버의 최종 순위
+pub struct SeasonStanding {
+    pub guild_id: u64,
+    // 공지 채널이 설정되지 않았으면 None
+    pub channel_id: Option<u64>,
+    pub ranking: Vec<RankingRow>,
+}
+
+// 곡 목록에 보여줄 곡 정보
+pub struct LibrarySong {
+    pub url: String,
+    // 첫 번째가 대표 제목, 나머지는 별칭
+    pub titles: Vec<String>,
+    pub tags: Vec<String>,
+    // 메타데이터를 아직 받지 않았으면 None
+    pub duration: Option<std::time::Duration>,
+}
+
+pub struct ImportReport {
+    // 새로 추가한 곡의 url (메타데이터를 받을 때 씀)
+    pub added: Vec<String>,
+    // 이미 DB에 있는 url이나 제목이라 건너뛴 줄의 제목
+    pub duplicates: Vec<String>,
+    // 곡은 추가했지만 이미 쓰이고 있어서 넣지 못한 별칭
+    pub skipped_aliases: Vec<String>,
+}
+
+pub struct ClaimReport {
+    pub claimed: usize,
+    // 이 서버 곡과 제목이 겹쳐 가져오지 못한 곡의 제목
+    pub conflicts: Vec<String>,
+}
+
+pub struct ChallengeInfo {
+    pub id: u64,
+    // 두 서버가 같이 플레이하는 게임 코드
+    pub code: String,
+    pub host_guild: u64,
+    pub guest_guild: u64,
+    // rfc3339 (UTC)
+    pub deadline: String,
+    pub closed: bool,
+}
+
+pub struct ChallengeResult {
+    pub guild_id: u64,
+    pub channel_id: u64,
+    pub points: i64,
+    pub solved: i64,
+    pub players: i64,
 }
 
 pub enum DBSuccess {
@@ -19,101 +205,1363 @@ pub enum DBSuccess {
 }
 
 pub async fn initialize(conn: &Connection) -> Result<()> {
-    conn.call(|conn| {
-        conn.execute(
-        "CREATE TABLE IF NOT EXISTS url (
-                id  INTEGER PRIMARY KEY, 
-                url TEXT
-            )", 
-            params![]
-        ).unwrap();
-        conn.execute(
-        "CREATE TABLE IF NOT EXISTS title (
-                id      INTEGER PRIMARY KEY, 
-                title   TEXT
-            )", 
-            params![]
-        ).unwrap();
+    conn.call(|conn| migrate(conn)).await
+}
+
+
+// 같은 서버가 소유한 곡끼리는 여러 url이 같은 정답 갖는 경우 비허용
+// 다른 서버 곡과는 제목이 겹쳐도 됨
+
+// 새 곡은 추가한 서버의 곡이 되고, 공유 풀에는 직접 공개해야 들어감
+// 이미 있는 곡에 제목을 더하는 건 그 곡을 소유한 서버만 가능
+pub async fn add_title_with_url(conn: &Connection, guild_id: u64, url: String, title: String) -> Result<DBSuccess, DBError> {
+    conn.call(move |conn| {
+        let _url_id = conn.query_row("SELECT id FROM url WHERE url = (?1)", params![url], |row| row.get::<usize, u64>(0)).optional()?;
+        if let Some(url_id) = _url_id {
+            if !is_owner(conn, guild_id, url_id)? {
+                return Err(DBError::NotOwner);
+            }
+        }
+        if title_used(conn, guild_id, &title)? {
+            return Err(DBError::TitleAlreadyUsed);
+        }
+        let tx = conn.transaction()?;
+        tx.execute("INSERT INTO title (title) VALUES (?1)", params![title])?;
+        let title_id = tx.query_row("SELECT last_insert_rowid()", params![], |row| row.get::<usize, u64>(0))?;
+        let (url_type, url_id) = match _url_id {
+            Some(url_id) => (0, url_id),
+            None => {
+                tx.execute("INSERT INTO url (url) VALUES (?1)", params![url])?;
+                let url_id = tx.query_row("SELECT last_insert_rowid()", params![], |row| row.get::<usize, u64>(0))?;
+                tx.execute("INSERT INTO song_owner (url_id, guild_id, shared) VALUES (?1, ?2, 0)", params![url_id, guild_id])?;
+                (1, url_id)
+            }
+        };
+        tx.execute("INSERT INTO url_title (url_id, title_id) VALUES (?1, ?2)", params![url_id, title_id])?;
+        tx.commit()?;
+        if url_type == 1 {
+            Ok(DBSuccess::NewUrl)
+        } else {
+            Ok(DBSuccess::ExistUrl)
+        }
+    }).await
+}
+
+// title로 곡 찾기 -> 없으면 실패
+// 소유 서버가 아니면 실패
+// alias가 서버 안에서 이미 쓰이면 실패
+// url_id 와 alias 연결
+pub async fn add_alias_with_title(conn: &Connection, guild_id: u64, title: String, alias: String) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let (_, url_id) = find_title(conn, guild_id, &title)?;
+        if !is_owner(conn, guild_id, url_id)? {
+            return Err(DBError::NotOwner);
+        }
+        if title_used(conn, guild_id, &alias)? {
+            return Err(DBError::TitleAlreadyUsed);
+        }
+        let tx = conn.transaction()?;
+        tx.execute("INSERT INTO title (title) VALUES (?1)", params![alias])?;
+        let alias_id = tx.query_row("SELECT last_insert_rowid()", params![], |row| row.get::<usize, u64>(0))?;
+        tx.execute("INSERT INTO url_title (url_id, title_id) VALUES (?1, ?2)", params![url_id, alias_id])?;
+        tx.commit()?;
+        Ok(())
+    }).await
+}
+
+// 마지막 제목까지 지워져 url이 고아가 되면 url도 지우고 그 url을 반환
+// 곡을 소유한 서버만 가능
+pub async fn remove_title(conn: &Connection, guild_id: u64, title: String) -> Result<Option<String>, DBError> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        let (title_id, url_id) = find_title(&tx, guild_id, &title)?;
+        if !is_owner(&tx, guild_id, url_id)? {
+            return Err(DBError::NotOwner);
+        }
+        tx.execute("DELETE FROM url_title WHERE title_id = (?1)", params![title_id])?;
+        tx.execute("DELETE FROM title WHERE id = (?1)", params![title_id])?;
+
+        let remain = tx.query_row("SELECT COUNT(*) FROM url_title WHERE url_id = (?1)", params![url_id], |row| row.get::<usize, i64>(0))?;
+        let orphan = if remain == 0 {
+            let url = tx.query_row("SELECT url FROM url WHERE id = (?1)", params![url_id], |row| row.get::<usize, String>(0))?;
+            delete_url_rows(&tx, url_id)?;
+            Some(url)
+        } else {
+            None
+        };
+        tx.commit()?;
+        Ok(orphan)
+    }).await
+}
+
+// 전체를 한 트랜잭션으로 등록
+// url이 이미 있거나 대표 제목을 서버에서 이미 쓰고 있으면 그 줄은 건너뛰고, 이미 쓰이는 별칭만 빠짐
+pub async fn import_songs(conn: &Connection, guild_id: u64, rows: Vec<SongRow>) -> Result<ImportReport, DBError> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        let mut report = ImportReport { added: vec![], duplicates: vec![], skipped_aliases: vec![] };
+        for row in rows {
+            let url_used = tx.query_row("SELECT id FROM url WHERE url = (?1)", params![row.url], |row| row.get::<usize, u64>(0))
+                .optional()?
+                .is_some();
+            if url_used || title_used(&tx, guild_id, &row.title)? {
+                report.duplicates.push(row.title);
+                continue;
+            }
+
+            tx.execute("INSERT INTO url (url) VALUES (?1)", params![row.url])?;
+            let url_id = tx.last_insert_rowid() as u64;
+            tx.execute("INSERT INTO song_owner (url_id, guild_id, shared) VALUES (?1, ?2, 0)", params![url_id, guild_id])?;
+            for title in std::iter::once(&row.title).chain(row.aliases.iter()) {
+                // 제목은 위에서 확인했으므로 여기서 걸리는 건 별칭뿐
+                if title_used(&tx, guild_id, title)? {
+                    report.skipped_aliases.push(title.to_owned());
+                    continue;
+                }
+                tx.execute("INSERT INTO title (title) VALUES (?1)", params![title])?;
+                tx.execute("INSERT INTO url_title (url_id, title_id) VALUES (?1, ?2)", params![url_id, tx.last_insert_rowid()])?;
+            }
+            for tag in row.tags.iter() {
+                tx.execute("INSERT OR IGNORE INTO tag (name) VALUES (?1)", params![tag])?;
+                let tag_id = tx.query_row("SELECT id FROM tag WHERE name = (?1)", params![tag], |row| row.get::<usize, u64>(0))?;
+                tx.execute("INSERT OR IGNORE INTO url_tag (url_id, tag_id) VALUES (?1, ?2)", params![url_id, tag_id])?;
+            }
+            for artist in row.artists.iter() {
+                tx.execute("INSERT OR IGNORE INTO url_artist (url_id, artist) VALUES (?1, ?2)", params![url_id, artist])?;
+            }
+            if let Some(year) = row.year {
+                tx.execute("INSERT INTO release_year (url_id, year) VALUES (?1, ?2)", params![url_id, year])?;
+            }
+            if let Some(lyrics) = row.lyrics.as_ref() {
+                tx.execute("INSERT INTO lyrics (url_id, lyrics) VALUES (?1, ?2)", params![url_id, lyrics])?;
+            }
+            if let Some(lrc) = row.lrc.as_ref() {
+                tx.execute("INSERT INTO synced_lyrics (url_id, lrc) VALUES (?1, ?2)", params![url_id, lrc])?;
+            }
+            if let Some(start) = row.clip_start {
+                tx.execute("INSERT INTO song_clip (url_id, start, duration) VALUES (?1, ?2, ?3)", params![url_id, start, row.clip_duration])?;
+            }
+            // 내보낸 파일을 다시 불러올 때 통계도 옮겨옴
+            if row.played > 0 {
+                tx.execute("INSERT INTO song_stats (url_id, played, solved) VALUES (?1, ?2, ?3)", params![url_id, row.played, row.solved])?;
+            }
+            report.added.push(row.url);
+        }
+        tx.commit()?;
+        Ok(report)
+    }).await
+}
+
+// import_songs로 다시 불러올 수 있는 형태로 곡을 읽음 (등록된 순)
+// include_shared면 공유 풀의 곡 전체(소유 서버가 없는 예전 곡 포함)도, 아니면 서버가 소유한 곡만
+// 공유 풀은 공개된 곡이라 서버가 공유 풀을 켜지 않았어도 내보낼 수 있음
+pub async fn export_songs(conn: &Connection, guild_id: u64, include_shared: bool) -> Result<Vec<SongRow>, DBError> {
+    conn.call(move |conn| {
+        let scope = match include_shared {
+            true => format!("url.id IN (SELECT url_id FROM song_owner WHERE guild_id = {} OR shared = 1)", guild_id),
+            false => format!("url.id IN (SELECT url_id FROM song_owner WHERE guild_id = {})", guild_id),
+        };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT url.id, url.url, release_year.year, lyrics.lyrics, synced_lyrics.lrc, song_stats.played, song_stats.solved FROM url
+                LEFT JOIN release_year ON release_year.url_id = url.id
+                LEFT JOIN lyrics ON lyrics.url_id = url.id
+                LEFT JOIN synced_lyrics ON synced_lyrics.url_id = url.id
+                LEFT JOIN song_stats ON song_stats.url_id = url.id
+                WHERE {}
+                ORDER BY url.id",
+            scope
+        ))?;
+        let urls = stmt.query_map(params![], |row| Ok((
+                row.get::<usize, u64>(0)?,
+                row.get::<usize, String>(1)?,
+                row.get::<usize, Option<i64>>(2)?,
+                row.get::<usize, Option<String>>(3)?,
+                row.get::<usize, Option<String>>(4)?,
+                row.get::<usize, Option<i64>>(5)?,
+                row.get::<usize, Option<i64>>(6)?,
+            )))?
+            .filter_map(|url| url.ok())
+            .collect::<Vec<(u64, String, Option<i64>, Option<String>, Option<String>, Option<i64>, Option<i64>)>>();
+
+        let mut rows = vec![];
+        for (id, url, year, lyrics, lrc, played, solved) in urls {
+            let mut titles = titles_of_url(conn, id)?;
+            // 제목이 없는 곡은 다시 불러올 수 없으므로 제외
+            if titles.is_empty() {
+                continue;
+            }
+            let title = titles.remove(0);
+            let clip = clip_of_url(conn, id)?;
+            rows.push(SongRow {
+                url,
+                title,
+                aliases: titles,
+                tags: tags_of_url(conn, id)?,
+                artists: artists_of_url(conn, id)?,
+                year,
+                lyrics,
+                lrc,
+                clip_start: clip.as_ref().map(|clip| clip.start),
+                clip_duration: clip.and_then(|clip| clip.duration),
+                played: played.unwrap_or(0),
+                solved: solved.unwrap_or(0),
+            });
+        }
+        Ok(rows)
+    }).await
+}
+
+// 퀴즈에서 출제된 곡마다 (url id, 누군가 맞혔는지)
+pub async fn record_song_stats(conn: &Connection, rounds: Vec<(u64, bool)>) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        for (url_id, solved) in rounds {
+            tx.execute(
+                "INSERT INTO song_stats (url_id, played, solved) VALUES (?1, 1, ?2)
+                    ON CONFLICT(url_id) DO UPDATE SET played = played + 1, solved = solved + excluded.solved",
+                params![url_id, solved as i64]
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }).await
+}
+
+// 곡에 딸린 제목과 정보를 모두 지우고 삭제된 url을 반환
+// 소유 서버가 없는 예전 곡은 /기존곡가져오기로 가져간 서버만 지울 수 있음
+pub async fn remove_song(conn: &Connection, guild_id: u64, title: String) -> Result<(String, Vec<String>), DBError> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        let (_, url_id) = find_title(&tx, guild_id, &title)?;
+        if !is_owner(&tx, guild_id, url_id)? {
+            return Err(DBError::NotOwner);
+        }
+        let url = tx.query_row("SELECT url FROM url WHERE id = (?1)", params![url_id], |row| row.get::<usize, String>(0))?;
+        let titles = titles_of_url(&tx, url_id)?;
+        delete_url_rows(&tx, url_id)?;
+        tx.commit()?;
+        Ok((url, titles))
+    }).await
+}
+
+// 서버가 고를 수 있는 곡의 WHERE 조건 (url 테이블 기준)
+// 자기 서버 곡과, 공유 풀을 켠 서버라면 공유된 곡 (소유 서버가 없는 예전 곡 포함)
+// guild_id는 정수라 쿼리에 바로 넣음
+fn visible_to(guild_id: u64) -> String {
+    format!(
+        "EXISTS (SELECT 1 FROM song_owner WHERE song_owner.url_id = url.id AND (song_owner.guild_id = {0}
+            OR (song_owner.shared = 1 AND COALESCE((SELECT use_shared FROM guild_settings WHERE guild_id = {0}), 0) = 1)))",
+        guild_id
+    )
+}
+
+// 소유 서버가 없는 예전 곡은 claim_legacy_songs로 가져가기 전까지 어느 서버의 곡도 아님 (읽기 전용)
+fn is_owner(conn: &rusqlite::Connection, guild_id: u64, url_id: u64) -> Result<bool, DBError> {
+    let owner = conn.query_row("SELECT guild_id FROM song_owner WHERE url_id = (?1)", params![url_id], |row| row.get::<usize, Option<u64>>(0))?;
+    Ok(owner == Some(guild_id))
+}
+
+// 곡 정보를 고칠 때 url로 곡을 찾고 소유 서버인지 확인
+fn owned_url_id(conn: &rusqlite::Connection, guild_id: u64, url: &str) -> Result<u64, DBError> {
+    let url_id = conn.query_row("SELECT id FROM url WHERE url = (?1)", params![url], |row| row.get::<usize, u64>(0))
+        .map_err(|why| or_not_found(why, DBError::UrlNotFound))?;
+    if !is_owner(conn, guild_id, url_id)? {
+        return Err(DBError::NotOwner);
+    }
+    Ok(url_id)
+}
+
+// 서버가 볼 수 있는 곡 중에서 제목으로 (title id, url id)를 찾음
+// 다른 서버 곡과 제목이 겹치면 자기 서버 곡을 우선
+fn find_title(conn: &rusqlite::Connection, guild_id: u64, title: &str) -> Result<(u64, u64), DBError> {
+    conn.query_row(
+        &format!(
+            "SELECT title.id, url.id FROM title
+                JOIN url_title ON url_title.title_id = title.id
+                JOIN url ON url.id = url_title.url_id
+                JOIN song_owner ON song_owner.url_id = url.id
+                WHERE title.title = (?1) AND {}
+                ORDER BY song_owner.guild_id IS (?2) DESC
+                LIMIT 1",
+            visible_to(guild_id)
+        ),
+        params![title, guild_id],
+        |row| Ok((row.get::<usize, u64>(0)?, row.get::<usize, u64>(1)?))
+    ).map_err(|why| or_not_found(why, DBError::TitleNotFound))
+}
+
+// 제목은 같은 서버가 소유한 곡끼리만 겹치지 않으면 됨
+fn title_used(conn: &rusqlite::Connection, guild_id: u64, title: &str) -> Result<bool, DBError> {
+    let used = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM title
+            JOIN url_title ON url_title.title_id = title.id
+            JOIN song_owner ON song_owner.url_id = url_title.url_id
+            WHERE title.title = (?1) AND song_owner.guild_id = (?2))",
+        params![title, guild_id],
+        |row| row.get::<usize, bool>(0)
+    )?;
+    Ok(used)
+}
+
+// 곡을 공유 풀에 공개하거나 내림 (소유 서버만 가능)
+pub async fn set_song_shared(conn: &Connection, guild_id: u64, title: String, shared: bool) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let (_, url_id) = find_title(conn, guild_id, &title)?;
+        if !is_owner(conn, guild_id, url_id)? {
+            return Err(DBError::NotOwner);
+        }
+        conn.execute("UPDATE song_owner SET shared = (?1) WHERE url_id = (?2)", params![shared as i64, url_id])?;
+        Ok(())
+    }).await
+}
+
+// 소유 서버가 없는 예전 곡을 이 서버 곡으로 가져옴 (먼저 가져간 서버가 소유)
+// 공유 풀에는 그대로 남아 다른 서버도 계속 쓸 수 있고, 고치거나 지우는 건 가져간 서버만 가능
+// title이 없으면 남은 예전 곡 전체를 가져오고, 이 서버 곡과 제목이 겹치는 곡은 건너뜀
+pub async fn claim_legacy_songs(conn: &Connection, guild_id: u64, title: Option<String>) -> Result<ClaimReport, DBError> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        let url_ids = match title {
+            Some(title) => {
+                let url_id = tx.query_row(
+                    "SELECT url_title.url_id FROM title
+                        JOIN url_title ON url_title.title_id = title.id
+                        JOIN song_owner ON song_owner.url_id = url_title.url_id
+                        WHERE title.title = (?1) AND song_owner.guild_id IS NULL
+                        LIMIT 1",
+                    params![title],
+                    |row| row.get::<usize, u64>(0)
+                ).map_err(|why| or_not_found(why, DBError::TitleNotFound))?;
+                vec![url_id]
+            },
+            None => {
+                let mut stmt = tx.prepare("SELECT url_id FROM song_owner WHERE guild_id IS NULL ORDER BY url_id")?;
+                let url_ids = stmt.query_map(params![], |row| row.get::<usize, u64>(0))?
+                    .filter_map(|url_id| url_id.ok())
+                    .collect::<Vec<u64>>();
+                url_ids
+            },
+        };
+
+        let mut report = ClaimReport { claimed: 0, conflicts: vec![] };
+        for url_id in url_ids {
+            let titles = titles_of_url(&tx, url_id)?;
+            let mut conflict = None;
+            for title in titles.iter() {
+                if title_used(&tx, guild_id, title)? {
+                    conflict = Some(title.to_owned());
+                    break;
+                }
+            }
+            if let Some(title) = conflict {
+                report.conflicts.push(title);
+                continue;
+            }
+            tx.execute("UPDATE song_owner SET guild_id = (?1) WHERE url_id = (?2) AND guild_id IS NULL", params![guild_id, url_id])?;
+            report.claimed += 1;
+        }
+        tx.commit()?;
+        Ok(report)
+    }).await
+}
+
+pub async fn set_use_shared(conn: &Connection, guild_id: u64, use_shared: bool) -> Result<(), DBError> {
+    conn.call(move |conn| {
         conn.execute(
-        "CREATE TABLE IF NOT EXISTS url_title (
-                url_id      INTEGER REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
-                title_id    INTEGER REFERENCES title(id) ON UPDATE CASCADE ON DELETE CASCADE,
-                PRIMARY KEY(url_id, title_id)
-            )", 
-            params![]
-        ).unwrap();
-    }).await;
+            "INSERT INTO guild_settings (guild_id, use_shared) VALUES (?1, ?2)
+                ON CONFLICT(guild_id) DO UPDATE SET use_shared = excluded.use_shared",
+            params![guild_id, use_shared as i64]
+        )?;
+        Ok(())
+    }).await
+}
 
+// foreign_keys가 꺼져 있어 CASCADE가 동작하지 않으므로 직접 지움
+fn delete_url_rows(conn: &rusqlite::Connection, url_id: u64) -> Result<(), DBError> {
+    conn.execute("DELETE FROM title WHERE id IN (SELECT title_id FROM url_title WHERE url_id = (?1))", params![url_id])?;
+    for table in ["url_title", "release_year", "url_artist", "lyrics", "synced_lyrics", "url_tag", "song_stats", "song_owner", "track_metadata", "song_clip"] {
+        conn.execute(&format!("DELETE FROM {} WHERE url_id = (?1)", table), params![url_id])?;
+    }
+    conn.execute("DELETE FROM url WHERE id = (?1)", params![url_id])?;
     Ok(())
 }
 
+fn tags_of_url(conn: &rusqlite::Connection, url_id: u64) -> Result<Vec<String>, DBError> {
+    let mut stmt = conn.prepare(
+        "SELECT tag.name FROM url_tag JOIN tag ON tag.id = url_tag.tag_id WHERE url_tag.url_id = (?1) ORDER BY tag.name"
+    )?;
+    let tags = stmt.query_map(params![url_id], |row| row.get::<usize, String>(0))?
+        .filter_map(|tag| tag.ok())
+        .collect();
+    Ok(tags)
+}
 
-// 현재 여러 url이 같은 정답 갖는 경우 비허용
-// 추후 중복 허용 필요가능성 있음
-
-pub async fn add_title_with_url(conn: &Connection, url: String, title: String) -> Result<DBSuccess, DBError> {
-    conn.call(move |conn| {
-        let _url_id = conn.query_row("SELECT id FROM url WHERE url = (?1)", params![url], |row| row.get::<usize, u64>(0));
-        let _title_id = conn.query_row("SELECT id FROM title WHERE title = (?1)", params![title], |row| row.get::<usize, u64>(0));
-        match _title_id {
-            Ok(_) => Err(DBError::TitleAlreadyUsed),
-            Err(_) => {
-                let tx = conn.transaction().unwrap();
-                tx.execute("INSERT INTO title (title) VALUES (?1)", params![title]).unwrap();
-                let title_id = tx.query_row("SELECT last_insert_rowid()", params![], |row| row.get::<usize, u64>(0)).unwrap();
-                let (url_type, url_id) = match _url_id {
-                    Ok(url_id) => (0, url_id),
-                    Err(_) => {
-                        tx.execute("INSERT INTO url (url) VALUES (?1)", params![url]).unwrap();
-                        (1, tx.query_row("SELECT last_insert_rowid()", params![], |row| row.get::<usize, u64>(0)).unwrap())
-                    }
-                };
-                tx.execute("INSERT INTO url_title (url_id, title_id) VALUES (?1, ?2)", params![url_id, title_id]).unwrap();
-                tx.commit().unwrap();
-                if url_type == 1 {
-                    Ok(DBSuccess::NewUrl)
-                } else {
-                    Ok(DBSuccess::ExistUrl)
-                }
+fn duration_of_url(conn: &rusqlite::Connection, url_id: u64) -> Option<std::time::Duration> {
+    conn.query_row("SELECT duration FROM track_metadata WHERE url_id = (?1)", params![url_id], |row| row.get::<usize, Option<f64>>(0))
+        .ok()
+        .flatten()
+        .map(std::time::Duration::from_secs_f64)
+}
+
+// filter가 있으면 제목이나 태그에 filter가 들어간 곡만 (등록된 순)
+// LIKE 검색어의 %, _를 글자 그대로 찾도록 escape (쿼리에 ESCAPE '\' 필요)
+fn like_pattern(query: &str) -> String {
+    let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+pub async fn library_songs(conn: &Connection, guild_id: u64, filter: Option<String>) -> Result<Vec<LibrarySong>, DBError> {
+    conn.call(move |conn| {
+        let urls = match filter {
+            Some(filter) => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT DISTINCT url.id, url.url FROM url
+                        LEFT JOIN url_title ON url_title.url_id = url.id
+                        LEFT JOIN title ON title.id = url_title.title_id
+                        LEFT JOIN url_tag ON url_tag.url_id = url.id
+                        LEFT JOIN tag ON tag.id = url_tag.tag_id
+                        WHERE (title.title LIKE (?1) ESCAPE '\\' OR tag.name LIKE (?1) ESCAPE '\\') AND {}
+                        ORDER BY url.id",
+                    visible_to(guild_id)
+                ))?;
+                let urls = stmt.query_map(params![like_pattern(&filter)], |row| Ok((row.get::<usize, u64>(0)?, row.get::<usize, String>(1)?)))?
+                    .filter_map(|url| url.ok())
+                    .collect::<Vec<(u64, String)>>();
+                urls
+            },
+            None => {
+                let mut stmt = conn.prepare(&format!("SELECT url.id, url.url FROM url WHERE {} ORDER BY url.id", visible_to(guild_id)))?;
+                let urls = stmt.query_map(params![], |row| Ok((row.get::<usize, u64>(0)?, row.get::<usize, String>(1)?)))?
+                    .filter_map(|url| url.ok())
+                    .collect::<Vec<(u64, String)>>();
+                urls
+            },
+        };
+
+        let mut songs = vec![];
+        for (id, url) in urls {
+            songs.push(LibrarySong {
+                url,
+                titles: titles_of_url(conn, id)?,
+                tags: tags_of_url(conn, id)?,
+                duration: duration_of_url(conn, id),
+            });
+        }
+        Ok(songs)
+    }).await
+}
+
+// 제목 자동완성용, 서버가 볼 수 있는 곡 중 query가 들어간 제목을 찾음
+pub async fn search_titles(conn: &Connection, guild_id: u64, query: String, limit: usize) -> Result<Vec<String>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT DISTINCT title.title FROM title
+                JOIN url_title ON url_title.title_id = title.id
+                JOIN url ON url.id = url_title.url_id
+                WHERE title.title LIKE (?1) ESCAPE '\\' AND {}
+                ORDER BY title.title LIMIT (?2)",
+            visible_to(guild_id)
+        ))?;
+        let titles = stmt.query_map(params![like_pattern(&query), limit], |row| row.get::<usize, String>(0))?
+            .filter_map(|title| title.ok())
+            .collect();
+        Ok(titles)
+    }).await
+}
+
+// 같은 곡에 등록된 모든 제목 (먼저 등록된 순)
+pub async fn titles_of_song(conn: &Connection, guild_id: u64, title: String) -> Result<(String, Vec<String>), DBError> {
+    conn.call(move |conn| {
+        let (_, url_id) = find_title(conn, guild_id, &title)?;
+        let url = conn.query_row("SELECT url FROM url WHERE id = (?1)", params![url_id], |row| row.get::<usize, String>(0))?;
+        Ok((url, titles_of_url(conn, url_id)?))
+    }).await
+}
+
+pub async fn set_release_year(conn: &Connection, guild_id: u64, url: String, year: i64) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let url_id = owned_url_id(conn, guild_id, &url)?;
+        conn.execute("INSERT OR REPLACE INTO release_year (url_id, year) VALUES (?1, ?2)", params![url_id, year])?;
+        Ok(())
+    }).await
+}
+
+// duration이 None이면 기본 길이로 출제
+pub async fn set_song_clip(conn: &Connection, guild_id: u64, url: String, start: u64, duration: Option<u64>) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let url_id = owned_url_id(conn, guild_id, &url)?;
+        conn.execute("INSERT OR REPLACE INTO song_clip (url_id, start, duration) VALUES (?1, ?2, ?3)", params![url_id, start, duration])?;
+        Ok(())
+    }).await
+}
+
+// 발매연도가 등록된 곡 중 count개를 무작위로 선택
+// 제목은 url에 연결된 제목 중 가장 먼저 등록된 것을 사용
+pub async fn random_dated_songs(conn: &Connection, guild_id: u64, count: usize) -> Result<Vec<DatedSong>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT url.url, 
+                (SELECT title.title FROM url_title JOIN title ON title.id = url_title.title_id 
+                    WHERE url_title.url_id = url.id ORDER BY title.id LIMIT 1),
+                release_year.year
+            FROM url JOIN release_year ON release_year.url_id = url.id
+            WHERE {}
+            ORDER BY RANDOM() LIMIT (?1)",
+            visible_to(guild_id)
+        ))?;
+        let songs = stmt.query_map(params![count], |row| {
+            Ok(DatedSong {
+                url: row.get(0)?,
+                title: row.get(1)?,
+                year: row.get(2)?,
+            })
+        })?
+        .filter_map(|song| song.ok())
+        .collect::<Vec<DatedSong>>();
+        Ok(songs)
+    }).await
+}
+
+fn titles_of_url(conn: &rusqlite::Connection, url_id: u64) -> Result<Vec<String>, DBError> {
+    let mut stmt = conn.prepare(
+        "SELECT title.title FROM url_title JOIN title ON title.id = url_title.title_id 
+            WHERE url_title.url_id = (?1) ORDER BY title.id"
+    )?;
+    let titles = stmt.query_map(params![url_id], |row| row.get::<usize, String>(0))?
+        .filter_map(|title| title.ok())
+        .collect();
+    Ok(titles)
+}
+
+fn artists_of_url(conn: &rusqlite::Connection, url_id: u64) -> Result<Vec<String>, DBError> {
+    let mut stmt = conn.prepare("SELECT artist FROM url_artist WHERE url_id = (?1)")?;
+    let artists = stmt.query_map(params![url_id], |row| row.get::<usize, String>(0))?
+        .filter_map(|artist| artist.ok())
+        .collect();
+    Ok(artists)
+}
+
+fn lyrics_of_url(conn: &rusqlite::Connection, url_id: u64) -> Option<String> {
+    conn.query_row("SELECT lyrics FROM lyrics WHERE url_id = (?1)", params![url_id], |row| row.get::<usize, String>(0)).ok()
+}
+
+fn quiz_song(conn: &rusqlite::Connection, id: u64, url: String) -> Result<QuizSong, DBError> {
+    Ok(QuizSong {
+        id,
+        url,
+        titles: titles_of_url(conn, id)?,
+        artists: artists_of_url(conn, id)?,
+        lyrics: lyrics_of_url(conn, id),
+        clip: clip_of_url(conn, id)?,
+    })
+}
+
+fn clip_of_url(conn: &rusqlite::Connection, url_id: u64) -> Result<Option<SongClip>, DBError> {
+    let clip = conn.query_row(
+        "SELECT start, duration FROM song_clip WHERE url_id = (?1)",
+        params![url_id],
+        |row| Ok(SongClip { start: row.get::<usize, u64>(0)?, duration: row.get::<usize, Option<u64>>(1)? })
+    ).optional()?;
+    Ok(clip)
+}
+
+pub async fn random_quiz_songs(conn: &Connection, guild_id: u64, count: usize) -> Result<Vec<QuizSong>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT url.id, url.url FROM url WHERE {} ORDER BY RANDOM() LIMIT (?1)",
+            visible_to(guild_id)
+        ))?;
+        let urls = stmt.query_map(params![count], |row| Ok((row.get::<usize, u64>(0)?, row.get::<usize, String>(1)?)))?
+            .filter_map(|url| url.ok())
+            .collect::<Vec<(u64, String)>>();
+        let songs = urls.into_iter()
+            .map(|(id, url)| quiz_song(conn, id, url))
+            .collect::<Result<Vec<QuizSong>, DBError>>()?;
+        Ok(songs.into_iter().filter(|song| !song.titles.is_empty()).collect())
+    }).await
+}
+
+// 가사가 등록된 곡 중 count개를 무작위로 선택
+pub async fn random_lyric_songs(conn: &Connection, guild_id: u64, count: usize) -> Result<Vec<QuizSong>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT url.id, url.url FROM url JOIN lyrics ON lyrics.url_id = url.id WHERE {} ORDER BY RANDOM() LIMIT (?1)",
+            visible_to(guild_id)
+        ))?;
+        let urls = stmt.query_map(params![count], |row| Ok((row.get::<usize, u64>(0)?, row.get::<usize, String>(1)?)))?
+            .filter_map(|url| url.ok())
+            .collect::<Vec<(u64, String)>>();
+        let songs = urls.into_iter()
+            .map(|(id, url)| quiz_song(conn, id, url))
+            .collect::<Result<Vec<QuizSong>, DBError>>()?;
+        Ok(songs.into_iter().filter(|song| !song.titles.is_empty()).collect())
+    }).await
+}
+
+// ids 순서대로 반환하고 DB에 없거나 서버가 볼 수 없는 id는 건너뜀
+pub async fn quiz_songs_by_ids(conn: &Connection, guild_id: u64, ids: Vec<u64>) -> Result<Vec<QuizSong>, DBError> {
+    conn.call(move |conn| {
+        let mut songs = vec![];
+        for id in ids {
+            let url = conn.query_row(
+                &format!("SELECT url.url FROM url WHERE url.id = (?1) AND {}", visible_to(guild_id)),
+                params![id],
+                |row| row.get::<usize, String>(0)
+            ).optional()?;
+            if let Some(url) = url {
+                songs.push(quiz_song(conn, id, url)?);
             }
         }
+        Ok(songs)
     }).await
 }
 
-// alias id 검색 -> 있으면 실패
-// title id 검색 -> 없으면 실패
-// 있으면 url_title에서 url_id 찾기
-// url_id 와 alias 연결
-pub async fn add_alias_with_title(conn: &Connection, title: String, alias: String) -> Result<(), DBError> {
+// 곡이 하나 이상 달린 태그 중에서 무작위로 뽑음
+pub async fn random_tags(conn: &Connection, guild_id: u64, count: usize) -> Result<Vec<String>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT name FROM tag WHERE id IN (SELECT url_tag.tag_id FROM url_tag JOIN url ON url.id = url_tag.url_id WHERE {})
+                ORDER BY RANDOM() LIMIT (?1)",
+            visible_to(guild_id)
+        ))?;
+        let tags = stmt.query_map(params![count], |row| row.get::<usize, String>(0))?
+            .filter_map(|tag| tag.ok())
+            .collect();
+        Ok(tags)
+    }).await
+}
+
+// exclude에 있는 곡(이미 출제된 곡)은 제외
+pub async fn random_tagged_song(conn: &Connection, guild_id: u64, tag: String, exclude: Vec<u64>) -> Result<QuizSong, DBError> {
     conn.call(move |conn| {
-        let _alias_id = conn.query_row("SELECT id FROM title WHERE title = (?1)", params![alias], |row| row.get::<usize, u64>(0));
-        let _title_id = conn.query_row("SELECT id FROM title WHERE title = (?1)", params![title], |row| row.get::<usize, u64>(0));
-        match _alias_id {
-            Ok(_) => return Err(DBError::TitleAlreadyUsed),
-            Err(_) => {
-                let tx = conn.transaction().unwrap();
-                let title_id = match _title_id {
-                    Ok(title_id) => title_id,
-                    Err(_) => return Err(DBError::TitleNotFound)
-                };
-                let url_id = tx.query_row("SELECT url_id FROM url_title WHERE title_id = (?1)", params![title_id], |row| row.get::<usize, u64>(0)).unwrap();
-                tx.execute("INSERT INTO title (title) VALUES (?1)", params![alias]).unwrap();
-                let alias_id = tx.query_row("SELECT last_insert_rowid()", params![], |row| row.get::<usize, u64>(0)).unwrap();
-                tx.execute("INSERT INTO url_title (url_id, title_id) VALUES (?1, ?2)", params![url_id, alias_id]).unwrap();
-                tx.commit().unwrap();
-                Ok(())
+        let mut stmt = conn.prepare(&format!(
+            "SELECT url.id, url.url FROM url
+                JOIN url_tag ON url_tag.url_id = url.id
+                JOIN tag ON tag.id = url_tag.tag_id
+                WHERE tag.name = (?1) AND {}
+                ORDER BY RANDOM()",
+            visible_to(guild_id)
+        ))?;
+        let urls = stmt.query_map(params![tag], |row| Ok((row.get::<usize, u64>(0)?, row.get::<usize, String>(1)?)))?
+            .filter_map(|url| url.ok())
+            .filter(|(id, _)| !exclude.contains(id))
+            .collect::<Vec<(u64, String)>>();
+        for (id, url) in urls {
+            let song = quiz_song(conn, id, url)?;
+            if !song.titles.is_empty() {
+                return Ok(song);
             }
         }
+        Err(DBError::UrlNotFound)
+    }).await
+}
+
+pub async fn insert_game_code(conn: &Connection, code: String, data: String) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        match conn.execute(
+            "INSERT INTO game_code (code, data, created_at) VALUES (?1, ?2, ?3)",
+            params![code, data, Utc::now().to_rfc3339()]
+        ) {
+            Ok(_) => Ok(()),
+            // 코드가 겹칠 때만 CodeAlreadyUsed, 나머지는 그대로 전달
+            Err(why) => match DBError::from(why) {
+                DBError::ConstraintViolation => Err(DBError::CodeAlreadyUsed),
+                why => Err(why),
+            },
+        }
+    }).await
+}
+
+pub async fn game_code_data(conn: &Connection, code: String) -> Result<String, DBError> {
+    conn.call(move |conn| {
+        conn.query_row("SELECT data FROM game_code WHERE code = (?1)", params![code], |row| row.get::<usize, String>(0))
+            .map_err(|why| or_not_found(why, DBError::CodeNotFound))
+    }).await
+}
+
+// 가수 이름은 한 곡에 여러 개 등록 가능 (별칭, 피처링 등)
+pub async fn add_artist(conn: &Connection, guild_id: u64, url: String, artist: String) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let url_id = owned_url_id(conn, guild_id, &url)?;
+        conn.execute("INSERT OR IGNORE INTO url_artist (url_id, artist) VALUES (?1, ?2)", params![url_id, artist])?;
+        Ok(())
+    }).await
+}
+
+// 가사는 곡당 하나만 저장하고 다시 등록하면 덮어씀
+pub async fn set_lyrics(conn: &Connection, guild_id: u64, url: String, lyrics: String) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let url_id = owned_url_id(conn, guild_id, &url)?;
+        conn.execute("INSERT OR REPLACE INTO lyrics (url_id, lyrics) VALUES (?1, ?2)", params![url_id, lyrics])?;
+        Ok(())
+    }).await
+}
+
+// 재생 중 표시할 LRC 가사
+pub async fn set_synced_lyrics(conn: &Connection, guild_id: u64, url: String, lrc: String) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let url_id = owned_url_id(conn, guild_id, &url)?;
+        conn.execute("INSERT OR REPLACE INTO synced_lyrics (url_id, lrc) VALUES (?1, ?2)", params![url_id, lrc])?;
+        Ok(())
+    }).await
+}
+
+// DB에 없는 곡이거나 가사가 없으면 None
+pub async fn synced_lyrics(conn: &Connection, url: String) -> Option<String> {
+    conn.call(move |conn| {
+        conn.query_row(
+            "SELECT synced_lyrics.lrc FROM url JOIN synced_lyrics ON synced_lyrics.url_id = url.id WHERE url.url = (?1)",
+            params![url],
+            |row| row.get::<usize, String>(0)
+        ).ok()
+    }).await
+}
+
+// DB에 없는 곡이거나 메타데이터를 아직 받지 않았으면 None
+pub async fn track_metadata(conn: &Connection, url: String) -> Option<Metadata> {
+    conn.call(move |conn| {
+        conn.query_row(
+            "SELECT track_metadata.title, track, artist, date, channel, duration, thumbnail FROM url
+                JOIN track_metadata ON track_metadata.url_id = url.id
+                WHERE url.url = (?1)",
+            params![url],
+            |row| Ok(Metadata {
+                title: row.get(0)?,
+                track: row.get(1)?,
+                artist: row.get(2)?,
+                date: row.get(3)?,
+                channel: row.get(4)?,
+                duration: row.get::<usize, Option<f64>>(5)?.map(std::time::Duration::from_secs_f64),
+                thumbnail: row.get(6)?,
+                channels: Some(2),
+                sample_rate: Some(SAMPLE_RATE),
+                source_url: Some(format!("{}{}", YOUTUBE_PREFIX, url)),
+                ..Default::default()
+            })
+        ).ok()
     }).await
 }
 
-pub async fn remove_title(conn: &Connection, title: String) -> Result<(), DBError> {
+// 다시 받으면 덮어씀
+pub async fn set_track_metadata(conn: &Connection, url: String, metadata: Metadata) -> Result<(), DBError> {
     conn.call(move |conn| {
-        let tx = conn.transaction().unwrap();
-        let title_id = tx.query_row("SELECT id FROM title WHERE title = (?1)", params![title], |row| row.get::<usize, u64>(0)).unwrap();
-        tx.execute("DELETE FROM url_title WHERE title_id = (?1)", params![title_id]).unwrap();
-        tx.execute("DELETE FROM title WHERE id = (?1)", params![title_id]).unwrap();
-        tx.commit().unwrap();
+        let url_id = match conn.query_row("SELECT id FROM url WHERE url = (?1)", params![url], |row| row.get::<usize, u64>(0)) {
+            Ok(url_id) => url_id,
+            Err(why) => return Err(or_not_found(why, DBError::UrlNotFound)),
+        };
+        conn.execute(
+            "INSERT OR REPLACE INTO track_metadata (url_id, title, track, artist, date, channel, duration, thumbnail)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                url_id, metadata.title, metadata.track, metadata.artist, metadata.date, metadata.channel,
+                metadata.duration.map(|duration| duration.as_secs_f64()), metadata.thumbnail
+            ]
+        )?;
+        Ok(())
+    }).await
+}
+
+// 태그는 이름이 같으면 같은 태그로 취급
+pub async fn add_tag(conn: &Connection, guild_id: u64, url: String, tag: String) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let url_id = owned_url_id(conn, guild_id, &url)?;
+        let tx = conn.transaction()?;
+        tx.execute("INSERT OR IGNORE INTO tag (name) VALUES (?1)", params![tag])?;
+        let tag_id = tx.query_row("SELECT id FROM tag WHERE name = (?1)", params![tag], |row| row.get::<usize, u64>(0))?;
+        tx.execute("INSERT OR IGNORE INTO url_tag (url_id, tag_id) VALUES (?1, ?2)", params![url_id, tag_id])?;
+        tx.commit()?;
         Ok(())
     }).await
-}
\ No newline at end of file
+}
+
+// 퀴즈가 끝날 때 전체 기록, 서버별 기록, 현재 시즌 기록에 더함
+pub async fn record_player_stats(conn: &Connection, guild_id: u64, season: String, records: Vec<PlayerRecord>) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        for record in records.iter() {
+            tx.execute(
+                "INSERT INTO player_stats (user_id, name, rounds, correct, reaction_ms, best_streak) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                    ON CONFLICT(user_id) DO UPDATE SET 
+                        name = excluded.name,
+                        rounds = rounds + excluded.rounds,
+                        correct = correct + excluded.correct,
+                        reaction_ms = reaction_ms + excluded.reaction_ms,
+                        best_streak = MAX(best_streak, excluded.best_streak)",
+                params![record.user_id, record.name, record.rounds, record.correct, record.reaction_ms, record.best_streak]
+            )?;
+            tx.execute(
+                "INSERT INTO guild_player_stats (guild_id, user_id, name, correct) VALUES (?1, ?2, ?3, ?4)
+                    ON CONFLICT(guild_id, user_id) DO UPDATE SET 
+                        name = excluded.name,
+                        correct = correct + excluded.correct",
+                params![guild_id, record.user_id, record.name, record.correct]
+            )?;
+            tx.execute(
+                "INSERT INTO season_score (season, guild_id, user_id, name, correct) VALUES (?1, ?2, ?3, ?4, ?5)
+                    ON CONFLICT(season, guild_id, user_id) DO UPDATE SET 
+                        name = excluded.name,
+                        correct = correct + excluded.correct",
+                params![season, guild_id, record.user_id, record.name, record.correct]
+            )?;
+            tx.execute(
+                "INSERT INTO player_bests (user_id, fastest_ms, wins) VALUES (?1, ?2, ?3)
+                    ON CONFLICT(user_id) DO UPDATE SET 
+                        fastest_ms = COALESCE(MIN(fastest_ms, excluded.fastest_ms), fastest_ms, excluded.fastest_ms),
+                        wins = wins + excluded.wins",
+                params![record.user_id, record.fastest_ms, record.won as i64]
+            )?;
+            for url_id in record.solved_urls.iter() {
+                tx.execute(
+                    "INSERT INTO player_tag (user_id, tag_id, correct) 
+                        SELECT (?1), tag_id, 1 FROM url_tag WHERE url_id = (?2) 
+                        ON CONFLICT(user_id, tag_id) DO UPDATE SET correct = correct + 1",
+                    params![record.user_id, url_id]
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }).await
+}
+
+pub async fn player_profile(conn: &Connection, user_id: u64) -> Result<PlayerProfile, DBError> {
+    conn.call(move |conn| {
+        let (name, rounds, correct, reaction_ms, best_streak) = conn.query_row(
+            "SELECT name, rounds, correct, reaction_ms, best_streak FROM player_stats WHERE user_id = (?1)",
+            params![user_id],
+            |row| Ok((row.get::<usize, String>(0)?, row.get::<usize, i64>(1)?, row.get::<usize, i64>(2)?, row.get::<usize, i64>(3)?, row.get::<usize, i64>(4)?))
+        ).map_err(|why| or_not_found(why, DBError::PlayerNotFound))?;
+
+        let rank = conn.query_row(
+            "SELECT COUNT(*) + 1 FROM player_stats WHERE correct > (?1)",
+            params![correct],
+            |row| row.get::<usize, i64>(0)
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT tag.name, player_tag.correct FROM player_tag JOIN tag ON tag.id = player_tag.tag_id 
+                WHERE player_tag.user_id = (?1) ORDER BY player_tag.correct DESC LIMIT 3"
+        )?;
+        let tags = stmt.query_map(params![user_id], |row| Ok((row.get::<usize, String>(0)?, row.get::<usize, i64>(1)?)))?
+            .filter_map(|tag| tag.ok())
+            .collect();
+
+        Ok(PlayerProfile { name, rounds, correct, reaction_ms, best_streak, tags, rank })
+    }).await
+}
+
+// guild_id가 None이면 전체 순위
+pub async fn player_ranking(conn: &Connection, guild_id: Option<u64>, limit: usize) -> Result<Vec<RankingRow>, DBError> {
+    conn.call(move |conn| {
+        let row_to_ranking = |row: &rusqlite::Row| Ok(RankingRow { name: row.get(0)?, correct: row.get(1)? });
+        let ranking = match guild_id {
+            Some(guild_id) => {
+                let mut stmt = conn.prepare(
+                    "SELECT name, correct FROM guild_player_stats WHERE guild_id = (?1) ORDER BY correct DESC LIMIT (?2)"
+                )?;
+                let rows = stmt.query_map(params![guild_id, limit], row_to_ranking)?
+                    .filter_map(|row| row.ok())
+                    .collect::<Vec<RankingRow>>();
+                rows
+            },
+            None => {
+                let mut stmt = conn.prepare(
+                    "SELECT name, correct FROM player_stats ORDER BY correct DESC LIMIT (?1)"
+                )?;
+                let rows = stmt.query_map(params![limit], row_to_ranking)?
+                    .filter_map(|row| row.ok())
+                    .collect::<Vec<RankingRow>>();
+                rows
+            },
+        };
+        Ok(ranking)
+    }).await
+}
+
+pub async fn set_season_channel(conn: &Connection, guild_id: u64, channel_id: u64) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO guild_settings (guild_id, season_channel) VALUES (?1, ?2)
+                ON CONFLICT(guild_id) DO UPDATE SET season_channel = excluded.season_channel",
+            params![guild_id, channel_id]
+        )?;
+        Ok(())
+    }).await
+}
+
+// 시즌 id는 YYYY-MM 형식이라 문자열 비교로 이전 시즌을 찾을 수 있음
+pub async fn unclosed_seasons(conn: &Connection, current: String) -> Result<Vec<String>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare("SELECT DISTINCT season FROM season_score WHERE season < (?1) ORDER BY season")?;
+        let seasons = stmt.query_map(params![current], |row| row.get::<usize, String>(0))?
+            .filter_map(|season| season.ok())
+            .collect();
+        Ok(seasons)
+    }).await
+}
+
+// 서버별 최종 순위를 보관용 테이블로 옮기고 진행 중 기록은 지움
+pub async fn close_season(conn: &Connection, season: String, limit: usize) -> Result<Vec<SeasonStanding>, DBError> {
+    conn.call(move |conn| {
+        let tx = conn.transaction()?;
+        let guild_ids = {
+            let mut stmt = tx.prepare("SELECT DISTINCT guild_id FROM season_score WHERE season = (?1)")?;
+            let guild_ids = stmt.query_map(params![season], |row| row.get::<usize, u64>(0))?
+                .filter_map(|guild_id| guild_id.ok())
+                .collect::<Vec<u64>>();
+            guild_ids
+        };
+
+        let mut standings = vec![];
+        for guild_id in guild_ids {
+            tx.execute(
+                "INSERT OR REPLACE INTO season_archive (season, guild_id, rank, user_id, name, correct)
+                    SELECT season, guild_id, RANK() OVER (ORDER BY correct DESC), user_id, name, correct
+                    FROM season_score WHERE season = (?1) AND guild_id = (?2)",
+                params![season, guild_id]
+            )?;
+            let channel_id = tx.query_row(
+                "SELECT season_channel FROM guild_settings WHERE guild_id = (?1)",
+                params![guild_id],
+                |row| row.get::<usize, u64>(0)
+            ).ok();
+            let ranking = {
+                let mut stmt = tx.prepare(
+                    "SELECT name, correct FROM season_archive WHERE season = (?1) AND guild_id = (?2) ORDER BY rank LIMIT (?3)"
+                )?;
+                let ranking = stmt.query_map(params![season, guild_id, limit], |row| Ok(RankingRow { name: row.get(0)?, correct: row.get(1)? }))?
+                    .filter_map(|row| row.ok())
+                    .collect::<Vec<RankingRow>>();
+                ranking
+            };
+            standings.push(SeasonStanding { guild_id, channel_id, ranking });
+        }
+        tx.execute("DELETE FROM season_score WHERE season = (?1)", params![season])?;
+        tx.commit()?;
+        Ok(standings)
+    }).await
+}
+
+// 진행 중인 시즌은 season_score, 끝난 시즌은 season_archive에 있음
+pub async fn season_ranking(conn: &Connection, season: String, guild_id: u64, limit: usize) -> Result<Vec<RankingRow>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT name, correct FROM season_score WHERE season = (?1) AND guild_id = (?2)
+            UNION ALL
+            SELECT name, correct FROM season_archive WHERE season = (?1) AND guild_id = (?2)
+            ORDER BY correct DESC LIMIT (?3)"
+        )?;
+        let ranking = stmt.query_map(params![season, guild_id, limit], |row| Ok(RankingRow { name: row.get(0)?, correct: row.get(1)? }))?
+            .filter_map(|row| row.ok())
+            .collect();
+        Ok(ranking)
+    }).await
+}
+
+pub async fn archived_seasons(conn: &Connection, guild_id: u64) -> Result<Vec<String>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare("SELECT DISTINCT season FROM season_archive WHERE guild_id = (?1) ORDER BY season DESC")?;
+        let seasons = stmt.query_map(params![guild_id], |row| row.get::<usize, String>(0))?
+            .filter_map(|season| season.ok())
+            .collect();
+        Ok(seasons)
+    }).await
+}
+
+pub async fn achievement_progress(conn: &Connection, user_id: u64) -> Result<AchievementProgress, DBError> {
+    conn.call(move |conn| {
+        let (correct, best_streak) = conn.query_row(
+            "SELECT correct, best_streak FROM player_stats WHERE user_id = (?1)",
+            params![user_id],
+            |row| Ok((row.get::<usize, i64>(0)?, row.get::<usize, i64>(1)?))
+        ).map_err(|why| or_not_found(why, DBError::PlayerNotFound))?;
+        let (fastest_ms, wins) = conn.query_row(
+            "SELECT fastest_ms, wins FROM player_bests WHERE user_id = (?1)",
+            params![user_id],
+            |row| Ok((row.get::<usize, Option<i64>>(0)?, row.get::<usize, i64>(1)?))
+        ).unwrap_or((None, 0));
+        Ok(AchievementProgress { correct, best_streak, fastest_ms, wins })
+    }).await
+}
+
+// (업적 id, 달성 시각)
+pub async fn unlocked_achievements(conn: &Connection, user_id: u64) -> Result<Vec<(String, String)>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare("SELECT achievement, unlocked_at FROM player_achievement WHERE user_id = (?1)")?;
+        let unlocked = stmt.query_map(params![user_id], |row| Ok((row.get::<usize, String>(0)?, row.get::<usize, String>(1)?)))?
+            .filter_map(|row| row.ok())
+            .collect();
+        Ok(unlocked)
+    }).await
+}
+
+// 이미 달성한 업적이면 false
+pub async fn unlock_achievement(conn: &Connection, user_id: u64, achievement: String) -> Result<bool, DBError> {
+    conn.call(move |conn| {
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO player_achievement (user_id, achievement, unlocked_at) VALUES (?1, ?2, ?3)",
+            params![user_id, achievement, Utc::now().to_rfc3339()]
+        )?;
+        Ok(inserted > 0)
+    }).await
+}
+
+pub async fn scoring_rule_data(conn: &Connection, guild_id: u64) -> Result<String, DBError> {
+    conn.call(move |conn| {
+        conn.query_row("SELECT data FROM scoring_rule WHERE guild_id = (?1)", params![guild_id], |row| row.get::<usize, String>(0))
+            .map_err(|why| or_not_found(why, DBError::RuleNotFound))
+    }).await
+}
+
+pub async fn set_scoring_rule_data(conn: &Connection, guild_id: u64, data: String) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        conn.execute("INSERT OR REPLACE INTO scoring_rule (guild_id, data) VALUES (?1, ?2)", params![guild_id, data])?;
+        Ok(())
+    }).await
+}
+
+// 기록이 없으면 None
+pub async fn coop_high_score(conn: &Connection, guild_id: u64) -> Option<i64> {
+    conn.call(move |conn| {
+        conn.query_row("SELECT score FROM coop_high_score WHERE guild_id = (?1)", params![guild_id], |row| row.get::<usize, i64>(0)).ok()
+    }).await
+}
+
+pub async fn set_coop_high_score(conn: &Connection, guild_id: u64, score: i64) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO coop_high_score (guild_id, score, achieved_at) VALUES (?1, ?2, ?3)",
+            params![guild_id, score, Utc::now().to_rfc3339()]
+        )?;
+        Ok(())
+    }).await
+}
+
+pub async fn season_channel(conn: &Connection, guild_id: u64) -> Option<u64> {
+    conn.call(move |conn| {
+        conn.query_row("SELECT season_channel FROM guild_settings WHERE guild_id = (?1)", params![guild_id], |row| row.get::<usize, u64>(0)).ok()
+    }).await
+}
+
+// 설정한 적 없는 서버는 도전을 받지 않음
+pub async fn accepts_challenges(conn: &Connection, guild_id: u64) -> bool {
+    conn.call(move |conn| {
+        conn.query_row("SELECT accept_challenges FROM guild_settings WHERE guild_id = (?1)", params![guild_id], |row| row.get::<usize, bool>(0))
+            .unwrap_or(false)
+    }).await
+}
+
+pub async fn set_accept_challenges(conn: &Connection, guild_id: u64, accept: bool) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO guild_settings (guild_id, accept_challenges) VALUES (?1, ?2)
+                ON CONFLICT(guild_id) DO UPDATE SET accept_challenges = excluded.accept_challenges",
+            params![guild_id, accept as i64]
+        )?;
+        Ok(())
+    }).await
+}
+
+pub async fn create_challenge(conn: &Connection, code: String, host_guild: u64, guest_guild: u64, deadline: String) -> Result<u64, DBError> {
+    conn.call(move |conn| {
+        conn.execute(
+            "INSERT INTO challenge (code, host_guild, guest_guild, deadline, closed) VALUES (?1, ?2, ?3, ?4, 0)",
+            params![code, host_guild, guest_guild, deadline]
+        )?;
+        Ok(conn.last_insert_rowid() as u64)
+    }).await
+}
+
+pub async fn challenge_info(conn: &Connection, id: u64) -> Result<ChallengeInfo, DBError> {
+    conn.call(move |conn| {
+        conn.query_row(
+            "SELECT id, code, host_guild, guest_guild, deadline, closed FROM challenge WHERE id = (?1)",
+            params![id],
+            |row| Ok(ChallengeInfo {
+                id: row.get(0)?,
+                code: row.get(1)?,
+                host_guild: row.get(2)?,
+                guest_guild: row.get(3)?,
+                deadline: row.get(4)?,
+                closed: row.get::<usize, i64>(5)? != 0,
+            })
+        ).map_err(|why| or_not_found(why, DBError::ChallengeNotFound))
+    }).await
+}
+
+// 서버당 한 번만 기록할 수 있음
+pub async fn insert_challenge_result(conn: &Connection, id: u64, result: ChallengeResult) -> Result<(), DBError> {
+    conn.call(move |conn| {
+        match conn.execute(
+            "INSERT INTO challenge_result (challenge_id, guild_id, channel_id, points, solved, players) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![id, result.guild_id, result.channel_id, result.points, result.solved, result.players]
+        ) {
+            Ok(_) => Ok(()),
+            // 서버마다 결과는 한 번만 (challenge_id, guild_id)
+            Err(why) => match DBError::from(why) {
+                DBError::ConstraintViolation => Err(DBError::ChallengeAlreadyPlayed),
+                why => Err(why),
+            },
+        }
+    }).await
+}
+
+pub async fn challenge_results(conn: &Connection, id: u64) -> Result<Vec<ChallengeResult>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT guild_id, channel_id, points, solved, players FROM challenge_result WHERE challenge_id = (?1)"
+        )?;
+        let results = stmt.query_map(params![id], |row| Ok(ChallengeResult {
+                guild_id: row.get(0)?,
+                channel_id: row.get(1)?,
+                points: row.get(2)?,
+                solved: row.get(3)?,
+                players: row.get(4)?,
+            }))?
+            .filter_map(|result| result.ok())
+            .collect();
+        Ok(results)
+    }).await
+}
+
+// 이미 닫힌 도전이면 false
+pub async fn close_challenge(conn: &Connection, id: u64) -> Result<bool, DBError> {
+    conn.call(move |conn| {
+        let updated = conn.execute("UPDATE challenge SET closed = 1 WHERE id = (?1) AND closed = 0", params![id])?;
+        Ok(updated > 0)
+    }).await
+}
+
+// deadline과 now 모두 UTC rfc3339라 문자열로 비교
+pub async fn expired_challenges(conn: &Connection, now: String) -> Result<Vec<u64>, DBError> {
+    conn.call(move |conn| {
+        let mut stmt = conn.prepare("SELECT id FROM challenge WHERE closed = 0 AND deadline < (?1)")?;
+        let ids = stmt.query_map(params![now], |row| row.get::<usize, u64>(0))?
+            .filter_map(|id| id.ok())
+            .collect();
+        Ok(ids)
+    }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: u64 = 1;
+    const OTHER_GUILD: u64 = 2;
+
+    async fn test_conn() -> Connection {
+        let conn = Connection::open_in_memory().await.unwrap();
+        initialize(&conn).await.unwrap();
+        conn
+    }
+
+    #[test]
+    fn no_rows_maps_to_not_found() {
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        let why = conn.query_row("SELECT id FROM url WHERE url = (?1)", params!["missing"], |row| row.get::<usize, u64>(0))
+            .unwrap_err();
+        assert!(matches!(DBError::from(why), DBError::NotFound));
+    }
+
+    #[test]
+    fn unique_violation_maps_to_constraint_violation() {
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        conn.execute("INSERT INTO game_code (code, data, created_at) VALUES ('abc', '', '')", params![]).unwrap();
+        let why = conn.execute("INSERT INTO game_code (code, data, created_at) VALUES ('abc', '', '')", params![])
+            .unwrap_err();
+        assert!(matches!(DBError::from(why), DBError::ConstraintViolation));
+    }
+
+    #[tokio::test]
+    async fn missing_rows_map_to_typed_errors() {
+        let conn = test_conn().await;
+        assert!(matches!(set_lyrics(&conn, GUILD, "missing".to_string(), "가사".to_string()).await, Err(DBError::UrlNotFound)));
+        assert!(matches!(game_code_data(&conn, "missing".to_string()).await, Err(DBError::CodeNotFound)));
+        assert!(matches!(challenge_info(&conn, 1).await, Err(DBError::ChallengeNotFound)));
+    }
+
+    #[tokio::test]
+    async fn remove_missing_title() {
+        let conn = test_conn().await;
+        assert!(matches!(remove_title(&conn, GUILD, "missing".to_string()).await, Err(DBError::TitleNotFound)));
+        assert!(matches!(remove_song(&conn, GUILD, "missing".to_string()).await, Err(DBError::TitleNotFound)));
+    }
+
+    #[tokio::test]
+    async fn duplicate_game_code() {
+        let conn = test_conn().await;
+        insert_game_code(&conn, "abc".to_string(), "{}".to_string()).await.unwrap();
+        assert!(matches!(insert_game_code(&conn, "abc".to_string(), "{}".to_string()).await, Err(DBError::CodeAlreadyUsed)));
+    }
+
+    #[tokio::test]
+    async fn duplicate_challenge_result() {
+        let conn = test_conn().await;
+        let id = create_challenge(&conn, "abc".to_string(), GUILD, OTHER_GUILD, Utc::now().to_rfc3339()).await.unwrap();
+        let result = || ChallengeResult { guild_id: GUILD, channel_id: 0, points: 0, solved: 0, players: 1 };
+        insert_challenge_result(&conn, id, result()).await.unwrap();
+        assert!(matches!(insert_challenge_result(&conn, id, result()).await, Err(DBError::ChallengeAlreadyPlayed)));
+    }
+
+    #[tokio::test]
+    async fn challenges_are_opt_in() {
+        let conn = test_conn().await;
+        assert!(!accepts_challenges(&conn, GUILD).await);
+        // 시즌 채널만 설정한 서버도 켜기 전에는 받지 않음
+        set_season_channel(&conn, GUILD, 10).await.unwrap();
+        assert!(!accepts_challenges(&conn, GUILD).await);
+        set_accept_challenges(&conn, GUILD, true).await.unwrap();
+        assert!(accepts_challenges(&conn, GUILD).await);
+        assert_eq!(season_channel(&conn, GUILD).await, Some(10));
+        set_accept_challenges(&conn, GUILD, false).await.unwrap();
+        assert!(!accepts_challenges(&conn, GUILD).await);
+    }
+
+    #[tokio::test]
+    async fn titles_are_unique_per_owner() {
+        let conn = test_conn().await;
+        add_title_with_url(&conn, GUILD, "a".to_string(), "title".to_string()).await.unwrap();
+        assert!(matches!(
+            add_title_with_url(&conn, GUILD, "b".to_string(), "title".to_string()).await,
+            Err(DBError::TitleAlreadyUsed)
+        ));
+        assert!(add_title_with_url(&conn, OTHER_GUILD, "b".to_string(), "title".to_string()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn search_treats_wildcards_literally() {
+        let conn = test_conn().await;
+        add_title_with_url(&conn, GUILD, "a".to_string(), "100%".to_string()).await.unwrap();
+        add_title_with_url(&conn, GUILD, "b".to_string(), "1000".to_string()).await.unwrap();
+        assert_eq!(search_titles(&conn, GUILD, "0%".to_string(), 10).await.unwrap(), vec!["100%".to_string()]);
+        assert!(search_titles(&conn, GUILD, "_0".to_string(), 10).await.unwrap().is_empty());
+        assert_eq!(library_songs(&conn, GUILD, Some("0%".to_string())).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn import_reports_taken_aliases() {
+        let conn = test_conn().await;
+        add_title_with_url(&conn, GUILD, "a".to_string(), "taken".to_string()).await.unwrap();
+        let row = SongRow {
+            url: "b".to_string(),
+            title: "title".to_string(),
+            aliases: vec!["taken".to_string(), "alias".to_string()],
+            ..Default::default()
+        };
+        let report = import_songs(&conn, GUILD, vec![row]).await.unwrap();
+        assert_eq!(report.added, vec!["b".to_string()]);
+        assert_eq!(report.skipped_aliases, vec!["taken".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn clip_round_trips_through_export() {
+        let conn = test_conn().await;
+        add_title_with_url(&conn, GUILD, "a".to_string(), "title".to_string()).await.unwrap();
+        assert!(matches!(set_song_clip(&conn, OTHER_GUILD, "a".to_string(), 30, None).await, Err(DBError::NotOwner)));
+        set_song_clip(&conn, GUILD, "a".to_string(), 30, Some(5)).await.unwrap();
+        let rows = export_songs(&conn, GUILD, false).await.unwrap();
+        assert_eq!((rows[0].clip_start, rows[0].clip_duration), (Some(30), Some(5)));
+
+        let other = test_conn().await;
+        import_songs(&other, GUILD, rows).await.unwrap();
+        let songs = random_quiz_songs(&other, GUILD, 1).await.unwrap();
+        let clip = songs[0].clip.as_ref().unwrap();
+        assert_eq!((clip.start, clip.duration), (30, Some(5)));
+    }
+
+    #[tokio::test]
+    async fn metadata_is_removed_with_song() {
+        let conn = test_conn().await;
+        let metadata = || Metadata { title: Some("title".to_string()), ..Default::default() };
+        // 곡으로 등록되지 않은 url은 저장하지 않음
+        assert!(matches!(set_track_metadata(&conn, "a".to_string(), metadata()).await, Err(DBError::UrlNotFound)));
+        add_title_with_url(&conn, GUILD, "a".to_string(), "title".to_string()).await.unwrap();
+        set_track_metadata(&conn, "a".to_string(), metadata()).await.unwrap();
+        assert_eq!(track_metadata(&conn, "a".to_string()).await.unwrap().title.as_deref(), Some("title"));
+        remove_song(&conn, GUILD, "title".to_string()).await.unwrap();
+        let remain = conn.call(|conn| conn.query_row("SELECT COUNT(*) FROM track_metadata", params![], |row| row.get::<usize, i64>(0)))
+            .await
+            .unwrap();
+        assert_eq!(remain, 0);
+    }
+
+    #[tokio::test]
+    async fn other_guild_cannot_edit_private_song() {
+        let conn = test_conn().await;
+        add_title_with_url(&conn, GUILD, "a".to_string(), "title".to_string()).await.unwrap();
+        // 공유하지 않은 곡은 다른 서버에서 보이지 않음
+        assert!(matches!(remove_title(&conn, OTHER_GUILD, "title".to_string()).await, Err(DBError::TitleNotFound)));
+
+        set_song_shared(&conn, GUILD, "title".to_string(), true).await.unwrap();
+        set_use_shared(&conn, OTHER_GUILD, true).await.unwrap();
+        assert!(matches!(remove_title(&conn, OTHER_GUILD, "title".to_string()).await, Err(DBError::NotOwner)));
+        assert!(matches!(
+            add_alias_with_title(&conn, OTHER_GUILD, "title".to_string(), "alias".to_string()).await,
+            Err(DBError::NotOwner)
+        ));
+        assert!(matches!(
+            add_title_with_url(&conn, OTHER_GUILD, "a".to_string(), "alias".to_string()).await,
+            Err(DBError::NotOwner)
+        ));
+    }
+
+    #[tokio::test]
+    async fn songs_by_ids_are_scoped_to_guild() {
+        let conn = test_conn().await;
+        add_title_with_url(&conn, GUILD, "a".to_string(), "title".to_string()).await.unwrap();
+        let ids = vec![1, 2];
+        assert_eq!(quiz_songs_by_ids(&conn, GUILD, ids.clone()).await.unwrap().len(), 1);
+        assert!(quiz_songs_by_ids(&conn, OTHER_GUILD, ids.clone()).await.unwrap().is_empty());
+
+        // 공유한 곡도 공유 풀을 켠 서버에서만 불러옴
+        set_song_shared(&conn, GUILD, "title".to_string(), true).await.unwrap();
+        assert!(quiz_songs_by_ids(&conn, OTHER_GUILD, ids.clone()).await.unwrap().is_empty());
+        set_use_shared(&conn, OTHER_GUILD, true).await.unwrap();
+        assert_eq!(quiz_songs_by_ids(&conn, OTHER_GUILD, ids).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn legacy_songs_can_be_claimed() {
+        let conn = test_conn().await;
+        add_title_with_url(&conn, GUILD, "a".to_string(), "title".to_string()).await.unwrap();
+        add_title_with_url(&conn, GUILD, "b".to_string(), "other".to_string()).await.unwrap();
+        // 소유 서버 없이 공유 풀에 있는 예전 곡으로 만듦
+        conn.call(|conn| conn.execute("UPDATE song_owner SET guild_id = NULL, shared = 1", params![])).await.unwrap();
+        assert!(matches!(set_release_year(&conn, GUILD, "a".to_string(), 2000).await, Err(DBError::NotOwner)));
+        add_title_with_url(&conn, OTHER_GUILD, "c".to_string(), "other".to_string()).await.unwrap();
+
+        let claimed = claim_legacy_songs(&conn, OTHER_GUILD, None).await.unwrap();
+        assert_eq!(claimed.claimed, 1);
+        assert_eq!(claimed.conflicts, vec!["other".to_string()]);
+        set_release_year(&conn, OTHER_GUILD, "a".to_string(), 2000).await.unwrap();
+        assert!(matches!(set_release_year(&conn, GUILD, "a".to_string(), 2000).await, Err(DBError::NotOwner)));
+        // 이미 다른 서버가 가져간 곡은 다시 가져올 수 없음
+        assert!(matches!(claim_legacy_songs(&conn, GUILD, Some("title".to_string())).await, Err(DBError::TitleNotFound)));
+        assert_eq!(claim_legacy_songs(&conn, GUILD, Some("other".to_string())).await.unwrap().claimed, 1);
+    }
+
+    #[tokio::test]
+    async fn other_guild_cannot_edit_song_info() {
+        let conn = test_conn().await;
+        add_title_with_url(&conn, GUILD, "a".to_string(), "title".to_string()).await.unwrap();
+        // 비공개 곡도, 공유 풀로 보이는 곡도 소유 서버만 고칠 수 있음
+        for shared in [false, true] {
+            set_song_shared(&conn, GUILD, "title".to_string(), shared).await.unwrap();
+            set_use_shared(&conn, OTHER_GUILD, shared).await.unwrap();
+            assert!(matches!(set_release_year(&conn, OTHER_GUILD, "a".to_string(), 2000).await, Err(DBError::NotOwner)));
+            assert!(matches!(add_artist(&conn, OTHER_GUILD, "a".to_string(), "artist".to_string()).await, Err(DBError::NotOwner)));
+            assert!(matches!(set_lyrics(&conn, OTHER_GUILD, "a".to_string(), "가사".to_string()).await, Err(DBError::NotOwner)));
+            assert!(matches!(set_synced_lyrics(&conn, OTHER_GUILD, "a".to_string(), "[00:01.00]가사".to_string()).await, Err(DBError::NotOwner)));
+            assert!(matches!(add_tag(&conn, OTHER_GUILD, "a".to_string(), "tag".to_string()).await, Err(DBError::NotOwner)));
+        }
+
+        set_release_year(&conn, GUILD, "a".to_string(), 2000).await.unwrap();
+        add_artist(&conn, GUILD, "a".to_string(), "artist".to_string()).await.unwrap();
+        set_lyrics(&conn, GUILD, "a".to_string(), "가사".to_string()).await.unwrap();
+        set_synced_lyrics(&conn, GUILD, "a".to_string(), "[00:01.00]가사".to_string()).await.unwrap();
+        add_tag(&conn, GUILD, "a".to_string(), "tag".to_string()).await.unwrap();
+    }
+}
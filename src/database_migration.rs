@@ -0,0 +1,310 @@
+use rusqlite::{ffi, Error, Result};
+use log::info;
+
+// 스키마 변경 단계
+// 새 변경은 항상 맨 뒤에 추가하고, 이미 배포된 단계는 고치지 않음
+// i번째 단계를 적용하면 user_version이 i + 1이 됨
+const MIGRATIONS: &[&str] = &[
+    // 1: 버전 관리 이전의 스키마
+    // 기존 DB에도 적용되도록 IF NOT EXISTS 사용
+    V1_INITIAL,
+    // 2: 곡별 출제, 정답 횟수
+    V2_SONG_STATS,
+    // 3: 서버별 곡 소유와 공유 풀
+    V3_SONG_OWNER,
+    // 4: 유튜브 메타데이터 (./tmp/<id>.json 대신)
+    V4_TRACK_METADATA,
+    // 5: 공유 풀은 서버가 직접 켜야 쓰도록 기본값을 끔
+    V5_SHARED_OPT_IN,
+    // 6: 서버 대항전은 도전을 받는 서버가 직접 켜야 받음
+    V6_CHALLENGE_OPT_IN,
+    // 7: 곡마다 정해 둔 출제 구간
+    V7_SONG_CLIP,
+];
+
+const V1_INITIAL: &str = "
+    CREATE TABLE IF NOT EXISTS url (
+        id  INTEGER PRIMARY KEY,
+        url TEXT
+    );
+    CREATE TABLE IF NOT EXISTS title (
+        id      INTEGER PRIMARY KEY,
+        title   TEXT
+    );
+    CREATE TABLE IF NOT EXISTS url_title (
+        url_id      INTEGER REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        title_id    INTEGER REFERENCES title(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        PRIMARY KEY(url_id, title_id)
+    );
+    CREATE TABLE IF NOT EXISTS release_year (
+        url_id  INTEGER PRIMARY KEY REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        year    INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS url_artist (
+        url_id  INTEGER REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        artist  TEXT,
+        PRIMARY KEY(url_id, artist)
+    );
+    CREATE TABLE IF NOT EXISTS lyrics (
+        url_id  INTEGER PRIMARY KEY REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        lyrics  TEXT
+    );
+    CREATE TABLE IF NOT EXISTS synced_lyrics (
+        url_id  INTEGER PRIMARY KEY REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        lrc     TEXT
+    );
+    CREATE TABLE IF NOT EXISTS tag (
+        id      INTEGER PRIMARY KEY,
+        name    TEXT UNIQUE
+    );
+    CREATE TABLE IF NOT EXISTS url_tag (
+        url_id  INTEGER REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        tag_id  INTEGER REFERENCES tag(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        PRIMARY KEY(url_id, tag_id)
+    );
+    CREATE TABLE IF NOT EXISTS player_stats (
+        user_id     INTEGER PRIMARY KEY,
+        name        TEXT,
+        rounds      INTEGER,
+        correct     INTEGER,
+        reaction_ms INTEGER,
+        best_streak INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS guild_player_stats (
+        guild_id    INTEGER,
+        user_id     INTEGER,
+        name        TEXT,
+        correct     INTEGER,
+        PRIMARY KEY(guild_id, user_id)
+    );
+    CREATE TABLE IF NOT EXISTS player_tag (
+        user_id INTEGER,
+        tag_id  INTEGER REFERENCES tag(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        correct INTEGER,
+        PRIMARY KEY(user_id, tag_id)
+    );
+    CREATE TABLE IF NOT EXISTS player_bests (
+        user_id     INTEGER PRIMARY KEY,
+        fastest_ms  INTEGER,
+        wins        INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS player_achievement (
+        user_id         INTEGER,
+        achievement     TEXT,
+        unlocked_at     TEXT,
+        PRIMARY KEY(user_id, achievement)
+    );
+    CREATE TABLE IF NOT EXISTS season_score (
+        season      TEXT,
+        guild_id    INTEGER,
+        user_id     INTEGER,
+        name        TEXT,
+        correct     INTEGER,
+        PRIMARY KEY(season, guild_id, user_id)
+    );
+    CREATE TABLE IF NOT EXISTS season_archive (
+        season      TEXT,
+        guild_id    INTEGER,
+        rank        INTEGER,
+        user_id     INTEGER,
+        name        TEXT,
+        correct     INTEGER,
+        PRIMARY KEY(season, guild_id, user_id)
+    );
+    CREATE TABLE IF NOT EXISTS guild_settings (
+        guild_id        INTEGER PRIMARY KEY,
+        season_channel  INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS scoring_rule (
+        guild_id    INTEGER PRIMARY KEY,
+        data        TEXT
+    );
+    CREATE TABLE IF NOT EXISTS coop_high_score (
+        guild_id    INTEGER PRIMARY KEY,
+        score       INTEGER,
+        achieved_at TEXT
+    );
+    CREATE TABLE IF NOT EXISTS challenge (
+        id          INTEGER PRIMARY KEY,
+        code        TEXT,
+        host_guild  INTEGER,
+        guest_guild INTEGER,
+        deadline    TEXT,
+        closed      INTEGER
+    );
+    CREATE TABLE IF NOT EXISTS challenge_result (
+        challenge_id    INTEGER REFERENCES challenge(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        guild_id        INTEGER,
+        channel_id      INTEGER,
+        points          INTEGER,
+        solved          INTEGER,
+        players         INTEGER,
+        PRIMARY KEY(challenge_id, guild_id)
+    );
+    CREATE TABLE IF NOT EXISTS game_code (
+        code        TEXT PRIMARY KEY,
+        data        TEXT,
+        created_at  TEXT
+    );
+";
+
+const V2_SONG_STATS: &str = "
+    CREATE TABLE song_stats (
+        url_id  INTEGER PRIMARY KEY REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        played  INTEGER,
+        solved  INTEGER
+    );
+";
+
+// 기존 곡은 모든 서버가 쓰던 곡이므로 소유 서버 없이 공유 풀에 넣음
+const V3_SONG_OWNER: &str = "
+    CREATE TABLE song_owner (
+        url_id      INTEGER PRIMARY KEY REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        guild_id    INTEGER,
+        shared      INTEGER
+    );
+    INSERT INTO song_owner (url_id, guild_id, shared) SELECT id, NULL, 1 FROM url;
+    ALTER TABLE guild_settings ADD COLUMN use_shared INTEGER DEFAULT 1;
+";
+
+// duration은 초 단위 실수
+const V4_TRACK_METADATA: &str = "
+    CREATE TABLE track_metadata (
+        url_id      INTEGER PRIMARY KEY REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        title       TEXT,
+        track       TEXT,
+        artist      TEXT,
+        date        TEXT,
+        channel     TEXT,
+        duration    REAL,
+        thumbnail   TEXT
+    );
+";
+
+// sqlite는 컬럼 기본값을 바꿀 수 없으므로 테이블을 다시 만듦
+// 이미 설정이 있는 서버는 값을 그대로 두고, 새로 설정이 생기는 서버만 꺼진 채로 시작
+const V5_SHARED_OPT_IN: &str = "
+    CREATE TABLE guild_settings_new (
+        guild_id        INTEGER PRIMARY KEY,
+        season_channel  INTEGER,
+        use_shared      INTEGER DEFAULT 0
+    );
+    INSERT INTO guild_settings_new (guild_id, season_channel, use_shared)
+        SELECT guild_id, season_channel, use_shared FROM guild_settings;
+    DROP TABLE guild_settings;
+    ALTER TABLE guild_settings_new RENAME TO guild_settings;
+";
+
+const V6_CHALLENGE_OPT_IN: &str = "
+    ALTER TABLE guild_settings ADD COLUMN accept_challenges INTEGER DEFAULT 0;
+";
+
+// duration이 없으면 기본 길이로 출제
+const V7_SONG_CLIP: &str = "
+    CREATE TABLE song_clip (
+        url_id      INTEGER PRIMARY KEY REFERENCES url(id) ON UPDATE CASCADE ON DELETE CASCADE,
+        start       INTEGER NOT NULL,
+        duration    INTEGER
+    );
+";
+
+pub fn schema_version(conn: &rusqlite::Connection) -> Result<usize> {
+    conn.pragma_query_value(None, "user_version", |row| row.get::<usize, i64>(0))
+        .map(|version| version as usize)
+}
+
+// 시작할 때 아직 적용되지 않은 단계를 순서대로 적용
+// 단계마다 트랜잭션으로 묶어 실패하면 그 단계 전체가 취소됨
+pub fn migrate(conn: &mut rusqlite::Connection) -> Result<()> {
+    let current = schema_version(conn)?;
+    if current > MIGRATIONS.len() {
+        // 더 새 버전의 봇이 만든 DB를 건드리지 않도록 시작하지 않음
+        return Err(Error::SqliteFailure(
+            ffi::Error::new(ffi::SQLITE_ERROR),
+            Some(format!("music.db 스키마 버전({})이 지원하는 버전({})보다 높습니다.", current, MIGRATIONS.len())),
+        ));
+    }
+
+    for (idx, migration) in MIGRATIONS.iter().enumerate().skip(current) {
+        let version = idx + 1;
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", version as i64)?;
+        tx.commit()?;
+        info!("music.db migrated to version {}", version);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::params;
+
+    // 버전 관리 이전에 쓰던 테이블 일부와 데이터
+    fn unversioned_db() -> rusqlite::Connection {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch("
+            CREATE TABLE url (id INTEGER PRIMARY KEY, url TEXT);
+            CREATE TABLE title (id INTEGER PRIMARY KEY, title TEXT);
+            CREATE TABLE url_title (url_id INTEGER, title_id INTEGER, PRIMARY KEY(url_id, title_id));
+            CREATE TABLE guild_settings (guild_id INTEGER PRIMARY KEY, season_channel INTEGER);
+            INSERT INTO url (id, url) VALUES (1, 'a');
+            INSERT INTO title (id, title) VALUES (1, 'title');
+            INSERT INTO url_title (url_id, title_id) VALUES (1, 1);
+            INSERT INTO guild_settings (guild_id, season_channel) VALUES (1, 10);
+        ").unwrap();
+        conn
+    }
+
+    #[test]
+    fn migrates_unversioned_db_with_data() {
+        let mut conn = unversioned_db();
+        assert_eq!(schema_version(&conn).unwrap(), 0);
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), MIGRATIONS.len());
+
+        let title = conn.query_row(
+            "SELECT title.title FROM url JOIN url_title ON url_title.url_id = url.id JOIN title ON title.id = url_title.title_id WHERE url.url = 'a'",
+            params![],
+            |row| row.get::<usize, String>(0)
+        ).unwrap();
+        assert_eq!(title, "title");
+        // 기존 곡은 소유 서버 없이 공유 풀에 들어감
+        let owner = conn.query_row("SELECT guild_id, shared FROM song_owner WHERE url_id = 1", params![], |row| {
+            Ok((row.get::<usize, Option<u64>>(0)?, row.get::<usize, i64>(1)?))
+        }).unwrap();
+        assert_eq!(owner, (None, 1));
+        // 기존 서버 설정은 유지되고 새 서버는 공유 풀이 꺼진 채로 시작
+        let existing = conn.query_row("SELECT season_channel, use_shared FROM guild_settings WHERE guild_id = 1", params![], |row| {
+            Ok((row.get::<usize, u64>(0)?, row.get::<usize, i64>(1)?))
+        }).unwrap();
+        assert_eq!(existing, (10, 1));
+        conn.execute("INSERT INTO guild_settings (guild_id, season_channel) VALUES (2, 20)", params![]).unwrap();
+        let use_shared = conn.query_row("SELECT use_shared FROM guild_settings WHERE guild_id = 2", params![], |row| row.get::<usize, i64>(0)).unwrap();
+        assert_eq!(use_shared, 0);
+    }
+
+    #[test]
+    fn migrates_from_first_version() {
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(V1_INITIAL).unwrap();
+        conn.pragma_update(None, "user_version", 1).unwrap();
+        conn.execute("INSERT INTO url (id, url) VALUES (1, 'a')", params![]).unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), MIGRATIONS.len());
+        let owners = conn.query_row("SELECT COUNT(*) FROM song_owner", params![], |row| row.get::<usize, i64>(0)).unwrap();
+        assert_eq!(owners, 1);
+        // 이미 적용된 단계는 다시 적용하지 않음
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), MIGRATIONS.len());
+    }
+
+    #[test]
+    fn refuses_newer_schema() {
+        let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", MIGRATIONS.len() as i64 + 1).unwrap();
+        assert!(migrate(&mut conn).is_err());
+        assert_eq!(schema_version(&conn).unwrap(), MIGRATIONS.len() + 1);
+    }
+}
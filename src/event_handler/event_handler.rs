@@ -16,6 +16,12 @@ use crate::{
         commands::*,
     },
     utils::guild_queue::{self},
+    quiz_handler::{
+        quiz_session::Guess,
+        private_answer::{open_answer_modal, submit_answer, ANSWER_BUTTON_ID, ANSWER_MODAL_ID},
+        season::spawn_season_task,
+        challenge::spawn_challenge_task,
+    },
     GuildQueueContainer
 };
 
@@ -27,6 +33,8 @@ impl EventHandler for DiscordEventHandler {
         println!("{} is connected!", ready.user.tag());
 
         guild_queue::initialize(&ctx).await;
+        spawn_season_task(&ctx);
+        spawn_challenge_task(&ctx);
 
         let commands = Command::get_global_application_commands(&ctx.http).await.unwrap();
 
@@ -46,7 +54,15 @@ impl EventHandler for DiscordEventHandler {
                 "launch" => launch::run(&ctx, command).await,
                 _ => execute_command(&ctx, command).await,
             }
-            Interaction::MessageComponent(component) => {},
+            Interaction::MessageComponent(component) => match component.data.custom_id.as_str() {
+                ANSWER_BUTTON_ID => open_answer_modal(&ctx, &component).await,
+                _ => {},
+            }
+            Interaction::Autocomplete(autocomplete) => execute_autocomplete(&ctx, autocomplete).await,
+            Interaction::ModalSubmit(modal) => match modal.data.custom_id.as_str() {
+                ANSWER_MODAL_ID => submit_answer(&ctx, &modal).await,
+                _ => {},
+            }
             _ => {},
         };
     }
@@ -64,6 +80,17 @@ impl EventHandler for DiscordEventHandler {
         let queue_lock = data.get(&gid).unwrap();
 
         let queue = queue_lock.read().await;
+        if let Some(sender) = queue.guess_sender.as_ref() {
+            if !message.author.bot {
+                let _ = sender.send(Guess {
+                    user: message.author.clone(),
+                    channel_id: message.channel_id,
+                    content: message.content.clone(),
+                    private: false,
+                });
+            }
+        }
+
         if let Some(guild_chat_channel) = queue.chat_channel {
             if message.channel_id == guild_chat_channel {
                 if let Some(track_handle) = queue.now_playing.clone() {
@@ -34,8 +34,10 @@ use env_logger::Env;
 mod command_handler;
 mod event_handler;
 mod database_handler;
+mod database_migration;
 mod connection_handler;
 mod utils;
+mod quiz_handler;
 
 use crate::utils::guild_queue::GuildQueue;
 
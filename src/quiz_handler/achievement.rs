@@ -0,0 +1,104 @@
+use serenity::{
+    client::Context,
+    model::id::ChannelId,
+};
+use tokio_rusqlite::Connection as Connection;
+
+use crate::database_handler::*;
+
+// 업적 목록
+// 새 업적은 여기에 추가하고 ALL에 넣으면 됨
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Achievement {
+    FirstAnswer,
+    Streak10,
+    Lightning,
+    Hundred,
+    // 원래 요청은 "서바이벌 게임 승리"였지만 탈락 방식의 서바이벌 모드가 없어서
+    // 가장 가까운 조건인 "2명 이상 참여한 퀴즈에서 단독 1등(협동 제외)"으로 대신함
+    // 서바이벌 모드가 생기면 이 업적은 두고 별도 업적을 추가 (id가 DB에 저장되어 있음)
+    Winner,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 5] = [
+        Achievement::FirstAnswer,
+        Achievement::Streak10,
+        Achievement::Lightning,
+        Achievement::Hundred,
+        Achievement::Winner,
+    ];
+
+    // DB에 저장되는 값이므로 바꾸면 안 됨
+    pub fn id(&self) -> &'static str {
+        match self {
+            Achievement::FirstAnswer => "first_answer",
+            Achievement::Streak10 => "streak_10",
+            Achievement::Lightning => "lightning",
+            Achievement::Hundred => "hundred",
+            Achievement::Winner => "winner",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Achievement::FirstAnswer => "첫 정답",
+            Achievement::Streak10 => "10연속 정답",
+            Achievement::Lightning => "번개",
+            Achievement::Hundred => "100곡",
+            Achievement::Winner => "우승",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::FirstAnswer => "처음으로 정답 맞히기",
+            Achievement::Streak10 => "한 판에서 10라운드 연속 정답",
+            Achievement::Lightning => "재생 시작 후 1초 안에 정답",
+            Achievement::Hundred => "누적 100곡 맞히기",
+            Achievement::Winner => "2명 이상 참여한 퀴즈에서 단독 1등",
+        }
+    }
+
+    // (현재 값, 목표 값)
+    pub fn progress(&self, progress: &AchievementProgress) -> (i64, i64) {
+        match self {
+            Achievement::FirstAnswer => (progress.correct.min(1), 1),
+            Achievement::Streak10 => (progress.best_streak.min(10), 10),
+            Achievement::Lightning => (progress.fastest_ms.map_or(0, |fastest| (fastest < 1000) as i64), 1),
+            Achievement::Hundred => (progress.correct.min(100), 100),
+            Achievement::Winner => (progress.wins.min(1), 1),
+        }
+    }
+
+    pub fn is_unlocked(&self, progress: &AchievementProgress) -> bool {
+        let (current, goal) = self.progress(progress);
+        current >= goal
+    }
+}
+
+// 퀴즈 기록을 저장한 뒤 호출
+// 새로 달성한 업적이 있으면 채팅으로 알림
+pub async fn check_achievements(ctx: &Context, conn: &Connection, channel_id: ChannelId, players: Vec<(u64, String)>) {
+    let mut announcements = vec![];
+    for (user_id, name) in players {
+        let progress = match achievement_progress(conn, user_id).await {
+            Ok(progress) => progress,
+            Err(_) => continue,
+        };
+        for achievement in Achievement::ALL.iter().filter(|achievement| achievement.is_unlocked(&progress)) {
+            if let Ok(true) = unlock_achievement(conn, user_id, achievement.id().to_owned()).await {
+                announcements.push(format!("🏆 {} - **{}** ({})", name, achievement.name(), achievement.description()));
+            }
+        }
+    }
+
+    if announcements.is_empty() {
+        return;
+    }
+    if let Err(why) = channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e.title("업적 달성!").description(announcements.join("\n")))
+    }).await {
+        println!("failed to announce achievements {:?}", why);
+    }
+}
@@ -0,0 +1,13 @@
+// 정답 비교용 정규화
+// 대소문자, 공백, 특수문자를 무시하고 비교함
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+pub fn is_correct(guess: &str, answers: &[String]) -> bool {
+    let guess = normalize(guess);
+    !guess.is_empty() && answers.iter().any(|answer| normalize(answer) == guess)
+}
@@ -0,0 +1,183 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Asia::Seoul;
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    model::id::{ChannelId, GuildId},
+};
+use tokio::time::sleep;
+use tokio_rusqlite::Connection as Connection;
+
+use crate::{
+    database_handler::*,
+    quiz_handler::quiz_session::QuizSession,
+    DBContainer,
+};
+
+// 만료된 도전 확인 간격(초)
+const CHALLENGE_CHECK_INTERVAL: u64 = 60;
+
+// ready는 재접속할 때마다 다시 불리므로 작업은 한 번만 띄움
+static CHALLENGE_TASK_STARTED: AtomicBool = AtomicBool::new(false);
+
+pub fn spawn_challenge_task(ctx: &Context) {
+    if CHALLENGE_TASK_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        loop {
+            close_expired_challenges(&ctx).await;
+            sleep(Duration::from_secs(CHALLENGE_CHECK_INTERVAL)).await;
+        }
+    });
+}
+
+// 기한 안에 한쪽만 플레이했으면 그대로 결과를 발표
+async fn close_expired_challenges(ctx: &Context) {
+    // 결과를 보내는 동안 data 잠금을 잡고 있지 않도록 연결을 복사해서 씀
+    let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+
+    let ids = match expired_challenges(&conn, Utc::now().to_rfc3339()).await {
+        Ok(ids) => ids,
+        Err(_) => return,
+    };
+    for id in ids {
+        finish_challenge(ctx, &conn, id).await;
+    }
+}
+
+pub fn deadline_text(deadline: &str) -> String {
+    match DateTime::parse_from_rfc3339(deadline) {
+        Ok(deadline) => deadline.with_timezone(&Seoul).format("%m월 %d일 %H:%M").to_string(),
+        Err(_) => deadline.to_owned(),
+    }
+}
+
+pub fn is_expired(challenge: &ChallengeInfo) -> bool {
+    challenge.closed || challenge.deadline < Utc::now().to_rfc3339()
+}
+
+// 도전 상대 서버에 알릴 채널
+// 시즌 채널이 있으면 그 채널, 없으면 서버의 시스템 채널
+pub async fn announce_channel(ctx: &Context, conn: &Connection, guild_id: u64) -> Option<ChannelId> {
+    if let Some(channel_id) = season_channel(conn, guild_id).await {
+        return Some(ChannelId(channel_id));
+    }
+    ctx.cache.guild(GuildId(guild_id)).and_then(|guild| guild.system_channel_id)
+}
+
+pub fn guild_name(ctx: &Context, guild_id: u64) -> String {
+    ctx.cache.guild(GuildId(guild_id))
+        .map(|guild| guild.name)
+        .unwrap_or_else(|| guild_id.to_string())
+}
+
+// 도전 퀴즈가 끝나면 호출
+// 두 서버가 모두 플레이했으면 바로 결과를 발표
+pub async fn submit_challenge_result(ctx: &Context, conn: &Connection, id: u64, session: &QuizSession) {
+    let result = ChallengeResult {
+        guild_id: session.gid.0,
+        channel_id: session.channel_id.0,
+        points: session.scores.values().map(|score| score.points).sum(),
+        solved: session.solved as i64,
+        players: session.scores.len() as i64,
+    };
+    let challenge = match challenge_info(conn, id).await {
+        Ok(challenge) => challenge,
+        Err(_) => return,
+    };
+    if challenge.closed {
+        if let Err(why) = session.channel_id.say(&ctx.http, format!("도전 #{}은 이미 마감되어 결과가 반영되지 않았습니다.", id)).await {
+            println!("failed to announce closed challenge {:?}", why);
+        }
+        return;
+    }
+    if insert_challenge_result(conn, id, result).await.is_err() {
+        println!("failed to record challenge result {}", id);
+        return;
+    }
+
+    match challenge_results(conn, id).await {
+        Ok(results) if results.len() >= 2 => finish_challenge(ctx, conn, id).await,
+        _ => {
+            let opponent = if challenge.host_guild == session.gid.0 { challenge.guest_guild } else { challenge.host_guild };
+            if let Err(why) = session.channel_id.say(&ctx.http, format!(
+                "도전 #{} 기록이 저장되었습니다. {}의 플레이가 끝나거나 {}에 마감되면 결과를 발표합니다.",
+                id, guild_name(ctx, opponent), deadline_text(&challenge.deadline)
+            )).await {
+                println!("failed to announce challenge record {:?}", why);
+            }
+        },
+    }
+}
+
+// 도전을 마감하고 두 서버에 결과를 올림
+async fn finish_challenge(ctx: &Context, conn: &Connection, id: u64) {
+    let challenge = match challenge_info(conn, id).await {
+        Ok(challenge) => challenge,
+        Err(_) => return,
+    };
+    // 동시에 마감되는 경우 한 번만 발표
+    match close_challenge(conn, id).await {
+        Ok(true) => (),
+        _ => return,
+    }
+    let results = challenge_results(conn, id).await.unwrap_or_default();
+    let embed = result_embed(ctx, &challenge, &results);
+
+    for guild_id in [challenge.host_guild, challenge.guest_guild] {
+        let channel_id = match results.iter().find(|result| result.guild_id == guild_id) {
+            Some(result) => Some(ChannelId(result.channel_id)),
+            None => announce_channel(ctx, conn, guild_id).await,
+        };
+        let channel_id = match channel_id {
+            Some(channel_id) => channel_id,
+            None => continue,
+        };
+        if let Err(why) = channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.clone_from(&embed);
+                e
+            })
+        }).await {
+            println!("failed to post challenge result {:?}", why);
+        }
+    }
+}
+
+// 맞힌 곡 수가 많은 쪽이 이기고, 같으면 점수 합으로 비교
+fn result_embed(ctx: &Context, challenge: &ChallengeInfo, results: &[ChallengeResult]) -> CreateEmbed {
+    let host = results.iter().find(|result| result.guild_id == challenge.host_guild);
+    let guest = results.iter().find(|result| result.guild_id == challenge.guest_guild);
+    let host_name = guild_name(ctx, challenge.host_guild);
+    let guest_name = guild_name(ctx, challenge.guest_guild);
+
+    let outcome = match (host, guest) {
+        (Some(host), Some(guest)) => match (host.solved, host.points).cmp(&(guest.solved, guest.points)) {
+            std::cmp::Ordering::Greater => format!("🏆 {} 승리!", host_name),
+            std::cmp::Ordering::Less => format!("🏆 {} 승리!", guest_name),
+            std::cmp::Ordering::Equal => "무승부!".to_owned(),
+        },
+        (Some(_), None) => format!("🏆 {} 부전승 ({} 미참가)", host_name, guest_name),
+        (None, Some(_)) => format!("🏆 {} 부전승 ({} 미참가)", guest_name, host_name),
+        (None, None) => "두 서버 모두 참가하지 않았습니다.".to_owned(),
+    };
+
+    let summary = |result: Option<&ChallengeResult>| match result {
+        Some(result) => format!("{}곡 / {}점\n참가 {}명", result.solved, result.points, result.players),
+        None => "미참가".to_owned(),
+    };
+
+    let mut embed = CreateEmbed::default();
+    embed.title(format!("서버 대항전 #{} 결과", challenge.id))
+        .description(outcome)
+        .field(host_name, summary(host), true)
+        .field(guest_name, summary(guest), true);
+    embed
+}
@@ -0,0 +1,161 @@
+use std::{
+    collections::HashMap,
+    time::Duration,
+};
+
+use futures::StreamExt;
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    model::{
+        application::interaction::InteractionResponseType,
+        id::{ChannelId, GuildId, UserId},
+    },
+};
+
+use crate::{
+    database_handler::DatedSong,
+    quiz_handler::{
+        clip::{play_clip, wait_clip_end, Transform},
+        quiz_session::{guild_queue_lock, open_guess_channel},
+    },
+};
+
+pub const CLIP_COUNT: usize = 4;
+const CLIP_START: u64 = 30;
+const CLIP_DURATION: u64 = 10;
+const ANSWER_TIME: u64 = 30;
+const LABELS: [&str; CLIP_COUNT] = ["A", "B", "C", "D"];
+
+// 연대순 라운드
+// 4곡을 차례로 짧게 들려준 뒤 select menu로 발매연도 순서를 맞추게 함
+// 점수는 올바른 위치에 놓인 곡의 수
+pub async fn run_chronology_round(ctx: &Context, gid: GuildId, channel_id: ChannelId, songs: &[DatedSong]) -> CreateEmbed {
+    // 진행 중인 퀴즈와 음성채널을 같이 쓰지 않도록 퀴즈처럼 정답 채널 자리를 차지
+    // 답은 select menu로 받으므로 채팅 정답은 읽지 않음
+    let queue_lock = guild_queue_lock(ctx, gid).await;
+    let (guess_channel, mut receiver) = match open_guess_channel(&queue_lock).await {
+        Some(opened) => opened,
+        None => {
+            let mut embed = CreateEmbed::default();
+            embed.title("연대순 라운드를 시작할 수 없습니다.")
+                .description("이미 진행 중인 퀴즈가 있습니다.");
+            return embed;
+        }
+    };
+    receiver.close();
+
+    for (idx, song) in songs.iter().enumerate() {
+        if let Err(why) = channel_id.say(&ctx.http, format!("{}번 곡 재생중", LABELS[idx])).await {
+            println!("failed to announce chronology clip {:?}", why);
+        }
+        match play_clip(ctx, gid, song.url.to_owned(), CLIP_START, CLIP_DURATION, Transform::Original).await {
+            Ok(handle) => wait_clip_end(&handle).await,
+            Err(why) => println!("chronology clip error {:?}", why),
+        }
+    }
+
+    let mut msg = match channel_id.send_message(&ctx.http, |m| {
+        m.content(format!("발매연도가 빠른 순서대로 골라주세요. ({}초)", ANSWER_TIME))
+            .components(|c| {
+                for position in 0..CLIP_COUNT {
+                    c.create_action_row(|row| {
+                        row.create_select_menu(|menu| {
+                            menu.custom_id(format!("chronology_{}", position))
+                                .placeholder(format!("{}번째", position + 1))
+                                .options(|opts| {
+                                    for (idx, label) in LABELS.iter().enumerate() {
+                                        opts.create_option(|opt| opt.label(label).value(idx));
+                                    }
+                                    opts
+                                })
+                        })
+                    });
+                }
+                c
+            })
+    }).await {
+        Ok(msg) => msg,
+        Err(why) => {
+            println!("failed to send chronology menu {:?}", why);
+            let mut embed = CreateEmbed::default();
+            embed.title("연대순 라운드를 진행할 수 없습니다.")
+                .description("선택 메뉴를 보내지 못했습니다.");
+            return embed;
+        }
+    };
+
+    let mut selections: HashMap<UserId, (String, [Option<usize>; CLIP_COUNT])> = HashMap::new();
+    let mut collector = msg.await_component_interactions(ctx)
+        .timeout(Duration::from_secs(ANSWER_TIME))
+        .build();
+
+    while let Some(interaction) = collector.next().await {
+        let position = interaction.data.custom_id
+            .trim_start_matches("chronology_")
+            .parse::<usize>()
+            .ok();
+        let choice = interaction.data.values.first()
+            .and_then(|value| value.parse::<usize>().ok());
+
+        if let (Some(position), Some(choice)) = (position, choice) {
+            if position < CLIP_COUNT && choice < CLIP_COUNT {
+                let entry = selections
+                    .entry(interaction.user.id)
+                    .or_insert((interaction.user.name.to_owned(), [None; CLIP_COUNT]));
+                entry.1[position] = Some(choice);
+            }
+        }
+
+        if let Err(why) = interaction.create_interaction_response(&ctx.http, |r| {
+            r.kind(InteractionResponseType::DeferredUpdateMessage)
+        }).await {
+            println!("failed to acknowledge chronology selection {:?}", why);
+        }
+    }
+
+    if let Err(why) = msg.edit(&ctx.http, |m| m.components(|c| c)).await {
+        println!("failed to close chronology menu {:?}", why);
+    }
+    drop(guess_channel);
+
+    let mut order = (0..songs.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|&idx| songs[idx].year);
+
+    let mut scores = selections.into_values()
+        .map(|(name, selection)| (name, score_order(songs, &order, &selection)))
+        .collect::<Vec<(String, usize)>>();
+    scores.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let answer = order.iter()
+        .enumerate()
+        .map(|(position, &idx)| format!("{}. {} - {} ({})", position + 1, LABELS[idx], songs[idx].title, songs[idx].year))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let result = if scores.is_empty() {
+        "참여자가 없습니다.".to_owned()
+    } else {
+        scores.iter()
+            .map(|(name, score)| format!("{}: {}/{}", name, score, CLIP_COUNT))
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    let mut embed = CreateEmbed::default();
+    embed.title("연대순 라운드 결과")
+        .field("정답", answer, false)
+        .field("점수", result, false);
+    embed
+}
+
+// 같은 연도의 곡은 서로 자리를 바꿔도 정답으로 인정
+fn score_order(songs: &[DatedSong], order: &[usize], selection: &[Option<usize>; CLIP_COUNT]) -> usize {
+    selection.iter()
+        .zip(order.iter())
+        .filter(|(chosen, &answer)| match chosen {
+            Some(chosen) => songs[*chosen].year == songs[answer].year,
+            None => false,
+        })
+        .count()
+}
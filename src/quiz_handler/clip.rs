@@ -0,0 +1,86 @@
+use std::{
+    io::{Error as IoError, ErrorKind},
+    time::Duration,
+};
+
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use serenity::{
+    client::Context,
+    model::id::GuildId,
+};
+use songbird::{
+    input::error::Result,
+    tracks::{PlayMode, TrackHandle},
+};
+use tokio::time::sleep;
+
+use crate::{
+    utils::audio_module::youtube_dl::ytdl_filtered,
+    DBContainer,
+};
+
+// 구간에 적용할 오디오 변형
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Transform {
+    #[default]
+    Original,
+    Reverse,
+    Fast,
+    Slow,
+}
+
+impl Transform {
+    pub fn random() -> Transform {
+        match rand::thread_rng().gen_range(0..4) {
+            0 => Transform::Original,
+            1 => Transform::Reverse,
+            2 => Transform::Fast,
+            _ => Transform::Slow,
+        }
+    }
+
+    fn filter(&self) -> Option<&'static str> {
+        match self {
+            Transform::Original => None,
+            Transform::Reverse => Some("areverse"),
+            Transform::Fast => Some("atempo=1.5"),
+            Transform::Slow => Some("atempo=0.75"),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::Original => "원곡",
+            Transform::Reverse => "역재생",
+            Transform::Fast => "빠르게",
+            Transform::Slow => "느리게",
+        }
+    }
+}
+
+// 퀴즈에서 쓰는 짧은 구간 재생
+// 음성채널 연결은 호출하는 쪽에서 먼저 해둬야 함
+pub async fn play_clip(ctx: &Context, gid: GuildId, url: String, start: u64, duration: u64, transform: Transform) -> Result<TrackHandle> {
+    let voice_manager = songbird::get(ctx).await.expect("Songbird Voice client placed in at initialisation.");
+    // 퀴즈 도중 봇이 음성채널에서 나갔을 수 있음
+    let handler_lock = voice_manager.get(gid)
+        .ok_or_else(|| IoError::new(ErrorKind::NotConnected, "not connected to a voice channel"))?;
+    // 다운로드 중에 data 잠금을 잡고 있지 않도록 연결을 복사해서 씀
+    let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+    let src = ytdl_filtered(&conn, url, start, duration, transform.filter()).await?;
+
+    let mut handler = handler_lock.lock().await;
+    Ok(handler.play_only_source(src))
+}
+
+// 트랙이 끝나거나 정지될 때까지 대기
+pub async fn wait_clip_end(handle: &TrackHandle) {
+    while let Ok(info) = handle.get_info().await {
+        match info.playing {
+            PlayMode::End | PlayMode::Stop => break,
+            _ => sleep(Duration::from_millis(500)).await,
+        }
+    }
+}
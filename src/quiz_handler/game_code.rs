@@ -0,0 +1,178 @@
+use rand::Rng;
+use serde::{Serialize, Deserialize};
+use tokio_rusqlite::Connection as Connection;
+
+use crate::{
+    database_handler::*,
+    quiz_handler::{
+        clip::Transform,
+        round::RoundKind,
+        quiz_session::{QuizEntry, QuizSession, QuizSettings},
+    },
+};
+
+const CODE_LENGTH: usize = 6;
+// 헷갈리기 쉬운 0, O, 1, I는 제외
+const CODE_CHARSET: &[u8] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+const MAX_RETRY: usize = 5;
+
+// game_code 테이블의 data 컬럼에 JSON으로 저장됨
+#[derive(Debug, Serialize, Deserialize)]
+struct GameCodeData {
+    settings: QuizSettings,
+    songs: Vec<GameCodeSong>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GameCodeSong {
+    // DB에 있는 곡이면 url 테이블의 id
+    #[serde(default)]
+    id: Option<u64>,
+    // 퀴즈팩에서만 쓰인 곡은 DB에서 다시 찾을 수 없으므로 문제를 그대로 저장
+    #[serde(default)]
+    pack: Option<GameCodePackSong>,
+    start: u64,
+    duration: u64,
+    #[serde(default)]
+    transform: Transform,
+    #[serde(default)]
+    hints: Vec<String>,
+    points: i64,
+    #[serde(default)]
+    kind: RoundKind,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GameCodePackSong {
+    url: String,
+    answers: Vec<String>,
+    #[serde(default)]
+    artists: Vec<String>,
+    lyrics: Option<String>,
+}
+
+fn generate_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..CODE_LENGTH)
+        .map(|_| CODE_CHARSET[rng.gen_range(0..CODE_CHARSET.len())] as char)
+        .collect()
+}
+
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+// 끝난 퀴즈를 코드로 저장
+pub async fn save_game_code(conn: &Connection, session: &QuizSession) -> Option<String> {
+    save_entries(conn, &session.entries, &session.settings).await
+}
+
+// DB 곡은 id만, 퀴즈팩 곡은 정답과 가수, 가사까지 저장
+pub async fn save_entries(conn: &Connection, entries: &[QuizEntry], settings: &QuizSettings) -> Option<String> {
+    let mut songs = vec![];
+    for entry in entries.iter() {
+        let pack = match entry.url_id {
+            Some(_) => None,
+            None => Some(GameCodePackSong {
+                url: entry.url.to_owned(),
+                answers: entry.answers.clone(),
+                artists: entry.artists.clone(),
+                lyrics: entry.lyrics.clone(),
+            }),
+        };
+        songs.push(GameCodeSong {
+            id: entry.url_id,
+            pack,
+            start: entry.start,
+            duration: entry.duration,
+            transform: entry.transform,
+            hints: entry.hints.clone(),
+            points: entry.points,
+            kind: entry.kind,
+        });
+    }
+
+    if songs.is_empty() {
+        return None;
+    }
+
+    // 투표로 고른 곡이 이미 entries에 들어 있으므로 다시 플레이할 때는 투표하지 않음
+    let mut settings = settings.clone();
+    settings.tag_draft = false;
+    let data = GameCodeData {
+        settings,
+        songs,
+    };
+    let data = serde_json::to_string(&data).unwrap();
+
+    for _ in 0..MAX_RETRY {
+        let code = generate_code();
+        if insert_game_code(conn, code.to_owned(), data.to_owned()).await.is_ok() {
+            return Some(code);
+        }
+    }
+    None
+}
+
+// 저장된 순서 그대로 문제를 복원
+// DB 곡의 정답은 현재 DB의 제목을 사용
+// 삭제된 곡과 guild_id 서버가 볼 수 없는 곡(다른 서버의 비공개 곡, 공유 풀을 켜지 않았을 때의 공유 곡)은 빼고
+// 뺀 곡 수를 함께 반환
+pub async fn load_game_code(conn: &Connection, guild_id: u64, code: String) -> Result<(Vec<QuizEntry>, QuizSettings, usize), String> {
+    let data = match game_code_data(conn, normalize_code(&code)).await {
+        Ok(data) => data,
+        Err(_) => return Err("존재하지 않는 코드입니다.".to_owned()),
+    };
+    let data: GameCodeData = serde_json::from_str(&data)
+        .map_err(|_| "코드 데이터가 손상되었습니다.".to_owned())?;
+
+    let ids = data.songs.iter().filter_map(|song| song.id).collect::<Vec<u64>>();
+    let found = quiz_songs_by_ids(conn, guild_id, ids).await
+        .map_err(|_| "곡을 불러오지 못했습니다.".to_owned())?;
+
+    // found는 ids 순서에서 빠진 곡만 건너뛴 것이므로 앞에서부터 id를 맞춰봄
+    let mut found = found.into_iter().peekable();
+    let mut entries = vec![];
+    let mut dropped = 0;
+    for saved in data.songs.into_iter() {
+        let mut entry = match (saved.id, saved.pack) {
+            (Some(id), _) => match found.next_if(|song| song.id == id) {
+                Some(song) => QuizEntry::from_song(song, saved.start, saved.duration, saved.transform),
+                None => {
+                    dropped += 1;
+                    continue;
+                },
+            },
+            (None, Some(pack)) => QuizEntry {
+                url_id: None,
+                url: pack.url,
+                answers: pack.answers,
+                start: saved.start,
+                duration: saved.duration,
+                hints: vec![],
+                points: 1,
+                transform: saved.transform,
+                artists: pack.artists,
+                lyrics: pack.lyrics,
+                kind: saved.kind,
+            },
+            (None, None) => return Err("코드 데이터가 손상되었습니다.".to_owned()),
+        };
+        entry.hints = saved.hints;
+        entry.points = saved.points;
+        entry.kind = saved.kind;
+        entries.push(entry);
+    }
+
+    Ok((entries, data.settings, dropped))
+}
+
+// 대항전 코드는 호스트 서버가 자기 곡으로 만든 것이므로 게스트 서버도 호스트 서버 기준으로 곡을 불러옴
+// 두 서버가 같은 문제를 풀어야 하므로 빠지는 곡이 있으면 시작하지 않음
+pub async fn load_challenge_code(conn: &Connection, challenge: &ChallengeInfo) -> Result<(Vec<QuizEntry>, QuizSettings), String> {
+    let (entries, settings, dropped) = load_game_code(conn, challenge.host_guild, challenge.code.to_owned()).await?;
+    if dropped > 0 {
+        return Err(format!("DB에서 {}곡을 찾을 수 없습니다.", dropped));
+    }
+    Ok((entries, settings))
+}
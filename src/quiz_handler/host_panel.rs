@@ -0,0 +1,295 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use serenity::{
+    builder::{CreateComponents, CreateEmbed},
+    client::Context,
+    model::{
+        application::{
+            component::ButtonStyle,
+            interaction::{
+                message_component::MessageComponentInteraction,
+                InteractionResponseType,
+            },
+        },
+        id::UserId,
+    },
+};
+use songbird::tracks::TrackHandle;
+use tokio::time::timeout;
+
+use crate::quiz_handler::{
+    clip::play_clip,
+    quiz_session::QuizSession,
+};
+
+// 호스트가 이 시간 동안 아무것도 누르지 않으면 퀴즈 종료
+const HOST_IDLE_TIMEOUT: u64 = 600;
+// select menu 옵션 최대 개수
+const MAX_CANDIDATES: usize = 25;
+
+// 호스트 진행 모드의 현재 상태
+struct HostState {
+    // 진행 중인 라운드 (아직 시작 전이면 None)
+    current: Option<usize>,
+    revealed: usize,
+    handle: Option<TrackHandle>,
+    // 되돌리기용 점수 기록 (user id, 이름, 점수)
+    awards: Vec<(UserId, String, i64)>,
+    last_action: String,
+}
+
+impl QuizSession {
+    // 호스트 진행 모드
+    // 라운드는 호스트가 패널 버튼을 눌렀을 때만 넘어가고 채점도 호스트가 직접 함
+    // 호스트가 듣고 판정하는 방식이라 음악을 재생하는 라운드만 지원 (가사, 썸네일 라운드는 시작 전에 거름)
+    pub async fn run_hosted(&mut self, ctx: &Context, host: UserId) {
+        let mut state = HostState {
+            current: None,
+            revealed: 0,
+            handle: None,
+            awards: vec![],
+            last_action: "다음 라운드를 눌러 시작하세요.".to_owned(),
+        };
+
+        let candidates = self.award_candidates(ctx).await;
+        let panel = self.panel_embed(&state, host);
+        let mut msg = match self.channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.clone_from(&panel);
+                e
+            })
+            .set_components(panel_components(&candidates))
+        }).await {
+            Ok(msg) => msg,
+            Err(why) => {
+                // 패널이 없으면 진행할 수 없으므로 퀴즈를 끝냄
+                println!("failed to send host panel {:?}", why);
+                return;
+            }
+        };
+
+        let mut collector = msg.await_component_interactions(ctx).build();
+
+        loop {
+            let interaction = match timeout(Duration::from_secs(HOST_IDLE_TIMEOUT), collector.next()).await {
+                Ok(Some(interaction)) => interaction,
+                _ => break,
+            };
+
+            if interaction.user.id != host {
+                if let Err(why) = interaction.create_interaction_response(&ctx.http, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| d.content("호스트만 사용할 수 있습니다.").ephemeral(true))
+                }).await {
+                    println!("failed to reject non-host {:?}", why);
+                }
+                continue;
+            }
+
+            // 곡을 받는 동안 3초 응답 제한을 넘길 수 있으므로 먼저 응답
+            if let Err(why) = interaction.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredUpdateMessage)
+            }).await {
+                println!("failed to defer host action {:?}", why);
+            }
+
+            let finished = self.handle_host_action(ctx, &interaction, &mut state).await;
+
+            let candidates = self.award_candidates(ctx).await;
+            let panel = self.panel_embed(&state, host);
+            if let Err(why) = msg.edit(&ctx.http, |m| {
+                m.set_embed(panel);
+                if finished {
+                    m.components(|c| c)
+                } else {
+                    m.set_components(panel_components(&candidates))
+                }
+            }).await {
+                println!("failed to update host panel {:?}", why);
+            }
+
+            if finished {
+                break;
+            }
+        }
+
+        if let Some(handle) = state.handle.take() {
+            let _ = handle.stop();
+        }
+        if let Some(idx) = state.current {
+            self.reveal_hosted(ctx, idx).await;
+        }
+    }
+
+    // 퀴즈가 끝났으면 true
+    async fn handle_host_action(&mut self, ctx: &Context, interaction: &MessageComponentInteraction, state: &mut HostState) -> bool {
+        match interaction.data.custom_id.as_str() {
+            "host_next" => {
+                if let Some(handle) = state.handle.take() {
+                    let _ = handle.stop();
+                }
+                if let Some(idx) = state.current {
+                    self.reveal_hosted(ctx, idx).await;
+                }
+                let next = state.current.map_or(0, |idx| idx + 1);
+                if next >= self.entries.len() {
+                    state.current = None;
+                    return true;
+                }
+                state.current = Some(next);
+                state.revealed = 0;
+                if let Err(why) = self.channel_id.say(&ctx.http, format!("{}/{} 라운드", next + 1, self.entries.len())).await {
+                    println!("failed to announce hosted round {:?}", why);
+                }
+                state.handle = self.play_hosted(ctx, next).await;
+                state.last_action = format!("{}라운드 시작", next + 1);
+            },
+            "host_replay" => {
+                if let Some(idx) = state.current {
+                    if let Some(handle) = state.handle.take() {
+                        let _ = handle.stop();
+                    }
+                    state.handle = self.play_hosted(ctx, idx).await;
+                    state.last_action = "다시 재생".to_owned();
+                }
+            },
+            "host_hint" => {
+                if let Some(idx) = state.current {
+                    match self.entries[idx].hints.get(state.revealed) {
+                        Some(hint) => {
+                            if let Err(why) = self.channel_id.say(&ctx.http, format!("힌트 {}. {}", state.revealed + 1, hint)).await {
+                                println!("failed to send hosted hint {:?}", why);
+                            }
+                            state.revealed += 1;
+                            state.last_action = format!("힌트 {} 공개", state.revealed);
+                        },
+                        None => state.last_action = "남은 힌트가 없습니다.".to_owned(),
+                    }
+                }
+            },
+            "host_award" => {
+                let points = state.current.map_or(1, |idx| self.entries[idx].points);
+                let target = interaction.data.values.first()
+                    .and_then(|value| value.parse::<u64>().ok())
+                    .map(UserId);
+                if let Some(user_id) = target {
+                    let name = user_id.to_user(ctx).await
+                        .map(|user| user.name)
+                        .unwrap_or_else(|_| user_id.0.to_string());
+                    self.award(user_id, &name, points);
+                    state.last_action = format!("{}에게 {}점", name, points);
+                    state.awards.push((user_id, name, points));
+                }
+            },
+            "host_undo" => {
+                match state.awards.pop() {
+                    Some((user_id, name, points)) => {
+                        self.award(user_id, &name, -points);
+                        state.last_action = format!("{}의 {}점 취소", name, points);
+                    },
+                    None => state.last_action = "취소할 점수가 없습니다.".to_owned(),
+                }
+            },
+            "host_end" => return true,
+            _ => (),
+        }
+        false
+    }
+
+    async fn play_hosted(&self, ctx: &Context, idx: usize) -> Option<TrackHandle> {
+        let entry = &self.entries[idx];
+        match play_clip(ctx, self.gid, entry.url.to_owned(), entry.start, entry.duration, entry.transform).await {
+            Ok(handle) => Some(handle),
+            Err(why) => {
+                println!("host clip error {:?}", why);
+                None
+            }
+        }
+    }
+
+    async fn reveal_hosted(&self, ctx: &Context, idx: usize) {
+        let entry = &self.entries[idx];
+        let answer = entry.answers.first().map(String::as_str).unwrap_or_default();
+        if let Err(why) = self.channel_id.say(&ctx.http, format!("정답: {}", answer)).await {
+            println!("failed to reveal hosted answer {:?}", why);
+        }
+    }
+
+    // 점수를 줄 수 있는 사람은 봇과 같은 음성채널에 있는 사람
+    async fn award_candidates(&self, ctx: &Context) -> Vec<(UserId, String)> {
+        let voice_manager = songbird::get(ctx).await.expect("Songbird Voice client placed in at initialisation.");
+        let channel = match voice_manager.get(self.gid) {
+            Some(call) => call.lock().await.current_channel(),
+            None => None,
+        };
+        let (channel, guild) = match (channel, ctx.cache.guild(self.gid)) {
+            (Some(channel), Some(guild)) => (channel, guild),
+            _ => return vec![],
+        };
+
+        guild.voice_states.iter()
+            .filter(|(_, state)| state.channel_id.map(|id| id.0) == Some(channel.0))
+            .filter_map(|(user_id, _)| guild.members.get(user_id))
+            .filter(|member| !member.user.bot)
+            .take(MAX_CANDIDATES)
+            .map(|member| (member.user.id, member.display_name().to_string()))
+            .collect()
+    }
+
+    fn panel_embed(&self, state: &HostState, host: UserId) -> CreateEmbed {
+        let round = match state.current {
+            Some(idx) => format!("{}/{} 라운드", idx + 1, self.entries.len()),
+            None => "대기 중".to_owned(),
+        };
+        let hints = match state.current {
+            Some(idx) => format!("{}/{}", state.revealed, self.entries[idx].hints.len()),
+            None => "-".to_owned(),
+        };
+        let ranking = self.ranking();
+        let scores = if ranking.is_empty() {
+            "-".to_owned()
+        } else {
+            ranking.iter()
+                .map(|score| format!("{} - {}점", score.name, score.points))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+
+        let mut embed = CreateEmbed::default();
+        embed.title("호스트 패널")
+            .description(format!("<@{}>만 조작할 수 있습니다.", host.0))
+            .field("진행", round, true)
+            .field("힌트", hints, true)
+            .field("최근 동작", &state.last_action, false)
+            .field("점수", scores, false);
+        embed
+    }
+}
+
+fn panel_components(candidates: &[(UserId, String)]) -> CreateComponents {
+    let mut components = CreateComponents::default();
+    components.create_action_row(|row| {
+        row.create_button(|b| b.custom_id("host_next").label("다음 라운드").style(ButtonStyle::Primary))
+            .create_button(|b| b.custom_id("host_replay").label("다시 듣기").style(ButtonStyle::Secondary))
+            .create_button(|b| b.custom_id("host_hint").label("힌트 공개").style(ButtonStyle::Secondary))
+            .create_button(|b| b.custom_id("host_undo").label("점수 취소").style(ButtonStyle::Secondary))
+            .create_button(|b| b.custom_id("host_end").label("종료").style(ButtonStyle::Danger))
+    });
+    // 후보가 없으면 select menu를 만들 수 없음
+    if !candidates.is_empty() {
+        components.create_action_row(|row| {
+            row.create_select_menu(|menu| {
+                menu.custom_id("host_award")
+                    .placeholder("점수 주기")
+                    .options(|opts| {
+                        for (user_id, name) in candidates.iter() {
+                            opts.create_option(|opt| opt.label(name).value(user_id.0));
+                        }
+                        opts
+                    })
+            })
+        });
+    }
+    components
+}
@@ -0,0 +1,51 @@
+use rand::seq::SliceRandom;
+
+use crate::quiz_handler::answer::normalize;
+
+// 빈칸을 만들 줄의 최소 단어 수
+const MIN_LINE_WORDS: usize = 3;
+// 한 번에 가리는 최대 단어 수
+const MAX_BLANK_WORDS: usize = 2;
+const BLANK: &str = "＿＿＿";
+
+// 빈칸이 뚫린 가사 한 줄
+pub struct LyricBlank {
+    pub line: String,
+    pub answers: Vec<String>,
+}
+
+// 가사에서 무작위로 한 줄을 골라 연속된 단어 몇 개를 가림
+// 정답은 가린 단어를 이어붙인 것으로 제목과 같은 방식으로 비교됨
+pub fn make_blank(lyrics: &str) -> Option<LyricBlank> {
+    let mut rng = rand::thread_rng();
+    let lines = lyrics.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<&str>>())
+        .filter(|words| words.len() >= MIN_LINE_WORDS)
+        .collect::<Vec<Vec<&str>>>();
+    // 가릴 수 있는 (줄, 시작 위치, 단어 수) 후보
+    // 특수문자만 있는 단어는 맞힐 수 없으므로 가리지 않음
+    let mut candidates = vec![];
+    for (line_idx, words) in lines.iter().enumerate() {
+        for length in 1..=MAX_BLANK_WORDS {
+            for start in 0..=words.len() - length {
+                if words[start..start + length].iter().all(|word| !normalize(word).is_empty()) {
+                    candidates.push((line_idx, start, length));
+                }
+            }
+        }
+    }
+    let (line_idx, start, length) = *candidates.choose(&mut rng)?;
+    let words = &lines[line_idx];
+
+    let answer = words[start..start + length].join(" ");
+    let line = words.iter()
+        .enumerate()
+        .map(|(idx, word)| if (start..start + length).contains(&idx) { BLANK } else { *word })
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    Some(LyricBlank {
+        line,
+        answers: vec![answer],
+    })
+}
@@ -0,0 +1,16 @@
+pub mod clip;
+pub mod chronology;
+pub mod answer;
+pub mod quiz_session;
+pub mod round;
+pub mod quiz_pack;
+pub mod game_code;
+pub mod win_condition;
+pub mod host_panel;
+pub mod private_answer;
+pub mod lyric;
+pub mod season;
+pub mod achievement;
+pub mod scoring;
+pub mod challenge;
+pub mod tag_draft;
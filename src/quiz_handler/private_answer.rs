@@ -0,0 +1,90 @@
+use serenity::{
+    client::Context,
+    model::application::{
+        component::{ActionRowComponent, InputTextStyle},
+        interaction::{
+            message_component::MessageComponentInteraction,
+            modal::ModalSubmitInteraction,
+            InteractionResponseType,
+        },
+    },
+};
+
+use crate::{
+    quiz_handler::quiz_session::Guess,
+    GuildQueueContainer,
+};
+
+pub const ANSWER_BUTTON_ID: &str = "quiz_answer";
+pub const ANSWER_MODAL_ID: &str = "quiz_answer_modal";
+const ANSWER_INPUT_ID: &str = "quiz_answer_input";
+
+// "답 제출" 버튼을 누르면 답을 입력할 modal을 띄움
+pub async fn open_answer_modal(ctx: &Context, component: &MessageComponentInteraction) {
+    if let Err(why) = component.create_interaction_response(&ctx.http, |r| {
+        r.kind(InteractionResponseType::Modal)
+            .interaction_response_data(|d| {
+                d.custom_id(ANSWER_MODAL_ID)
+                    .title("답 제출")
+                    .components(|c| {
+                        c.create_action_row(|row| {
+                            row.create_input_text(|input| {
+                                input.custom_id(ANSWER_INPUT_ID)
+                                    .label("정답")
+                                    .style(InputTextStyle::Short)
+                                    .max_length(100)
+                                    .required(true)
+                            })
+                        })
+                    })
+            })
+    }).await {
+        println!("failed to open answer modal {:?}", why);
+    }
+}
+
+// modal로 제출된 답을 진행 중인 라운드로 전달
+pub async fn submit_answer(ctx: &Context, modal: &ModalSubmitInteraction) {
+    let content = modal.data.components.iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == ANSWER_INPUT_ID => Some(input.value.to_owned()),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let delivered = match modal.guild_id {
+        Some(gid) => {
+            let data = ctx.data.read().await;
+            let data = data.get::<GuildQueueContainer>().unwrap();
+            match data.get(&gid) {
+                Some(queue_lock) => {
+                    let queue = queue_lock.read().await;
+                    match queue.guess_sender.as_ref() {
+                        Some(sender) => sender.send(Guess {
+                            user: modal.user.clone(),
+                            channel_id: modal.channel_id,
+                            content: content.to_owned(),
+                            private: true,
+                        }).is_ok(),
+                        None => false,
+                    }
+                },
+                None => false,
+            }
+        },
+        None => false,
+    };
+
+    let reply = if delivered {
+        format!("답을 제출했습니다: {}", content)
+    } else {
+        "진행 중인 퀴즈가 없습니다.".to_owned()
+    };
+    if let Err(why) = modal.create_interaction_response(&ctx.http, |r| {
+        r.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|d| d.content(reply).ephemeral(true))
+    }).await {
+        println!("failed to respond answer modal {:?}", why);
+    }
+}
@@ -0,0 +1,198 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    quiz_handler::{
+        answer::normalize,
+        clip::Transform,
+        lyric::make_blank,
+        round::RoundKind,
+        quiz_session::{QuizEntry, QuizSettings, DEFAULT_CLIP_DURATION},
+        win_condition::WinCondition,
+    },
+    utils::url_checker::url_checker,
+};
+
+pub const MAX_PACK_SIZE: u64 = 1024 * 1024;
+pub const MAX_PACK_SONGS: usize = 100;
+const MAX_CLIP_DURATION: u64 = 60;
+const MAX_ANSWER_TIME: u64 = 120;
+// 곡 길이를 미리 알 수 없으므로 긴 영상까지 허용할 만큼만 제한
+const MAX_CLIP_START: u64 = 3 * 60 * 60;
+const MAX_TIME_LIMIT: u64 = 60;
+
+// 퀴즈팩 JSON 형식
+// {
+//     "name": "퀴즈 이름",
+//     "settings": { "answer_time": 30, "hint_interval": 10, "win_condition": { "kind": "first_to", "points": 5 }, "private_answers": false, "lyric_audio": false },
+//     "songs": [
+//         { "url": "...", "answers": ["..."], "artists": ["..."], "lyrics": "...", "thumbnail": false, "start": 30, "duration": 20, "hints": ["..."], "points": 1, "transform": "reverse" }
+//     ]
+// }
+// artists가 있으면 제목과 가수를 따로 맞히는 라운드가 됨
+// lyrics가 있으면 가사 빈칸 라운드가 됨 (줄바꿈으로 줄 구분)
+// thumbnail이 true면 음악 대신 썸네일을 보고 맞히는 라운드가 됨
+#[derive(Debug, Deserialize)]
+struct RawQuizPack {
+    name: Option<String>,
+    #[serde(default)]
+    settings: RawPackSettings,
+    // 곡마다 오류를 따로 보고하기 위해 개별적으로 파싱함
+    songs: Vec<Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawPackSettings {
+    answer_time: Option<u64>,
+    hint_interval: Option<u64>,
+    win_condition: Option<WinCondition>,
+    #[serde(default)]
+    private_answers: bool,
+    #[serde(default)]
+    lyric_audio: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawPackSong {
+    url: String,
+    #[serde(default)]
+    answers: Vec<String>,
+    #[serde(default)]
+    artists: Vec<String>,
+    lyrics: Option<String>,
+    #[serde(default)]
+    thumbnail: bool,
+    #[serde(default)]
+    start: u64,
+    duration: Option<u64>,
+    #[serde(default)]
+    hints: Vec<String>,
+    points: Option<i64>,
+    #[serde(default)]
+    transform: Transform,
+}
+
+pub struct QuizPack {
+    pub name: String,
+    pub settings: QuizSettings,
+    pub entries: Vec<QuizEntry>,
+    // 건너뛴 곡의 오류 메시지
+    pub errors: Vec<String>,
+}
+
+pub fn load_quiz_pack(raw: &[u8]) -> Result<QuizPack, String> {
+    let pack: RawQuizPack = serde_json::from_slice(raw)
+        .map_err(|why| format!("JSON 형식이 올바르지 않습니다. ({})", why))?;
+
+    if pack.songs.is_empty() {
+        return Err("곡이 없습니다.".to_owned());
+    }
+    if pack.songs.len() > MAX_PACK_SONGS {
+        return Err(format!("곡은 최대 {}개까지 넣을 수 있습니다.", MAX_PACK_SONGS));
+    }
+
+    let mut settings = QuizSettings::default();
+    if let Some(answer_time) = pack.settings.answer_time {
+        if answer_time == 0 || answer_time > MAX_ANSWER_TIME {
+            return Err(format!("answer_time은 1~{}초 사이여야 합니다.", MAX_ANSWER_TIME));
+        }
+        settings.answer_time = answer_time;
+    }
+    if let Some(hint_interval) = pack.settings.hint_interval {
+        if hint_interval == 0 {
+            return Err("hint_interval은 1초 이상이어야 합니다.".to_owned());
+        }
+        settings.hint_interval = hint_interval;
+    }
+    if let Some(win_condition) = pack.settings.win_condition {
+        match win_condition {
+            WinCondition::FirstTo { points } if points <= 0 => {
+                return Err("win_condition의 points는 1 이상이어야 합니다.".to_owned());
+            },
+            WinCondition::TimeAttack { minutes } | WinCondition::Coop { minutes } if minutes == 0 || minutes > MAX_TIME_LIMIT => {
+                return Err(format!("win_condition의 minutes는 1~{}분 사이여야 합니다.", MAX_TIME_LIMIT));
+            },
+            _ => (),
+        }
+        settings.win_condition = win_condition;
+    }
+    settings.private_answers = pack.settings.private_answers;
+    settings.lyric_audio = pack.settings.lyric_audio;
+
+    let mut entries = vec![];
+    let mut errors = vec![];
+    for (idx, song) in pack.songs.into_iter().enumerate() {
+        match parse_entry(song) {
+            Ok(entry) => entries.push(entry),
+            Err(why) => errors.push(format!("{}번: {}", idx + 1, why)),
+        }
+    }
+
+    Ok(QuizPack {
+        name: pack.name.unwrap_or_else(|| "이름 없는 퀴즈".to_owned()),
+        settings,
+        entries,
+        errors,
+    })
+}
+
+fn parse_entry(song: Value) -> Result<QuizEntry, String> {
+    let song: RawPackSong = serde_json::from_value(song)
+        .map_err(|why| format!("형식 오류 ({})", why))?;
+
+    let url = url_checker(&song.url).ok_or("유효한 url이 아닙니다.".to_owned())?;
+
+    let answers = song.answers.into_iter()
+        .filter(|answer| !normalize(answer).is_empty())
+        .collect::<Vec<String>>();
+    if answers.is_empty() {
+        return Err("정답이 없습니다.".to_owned());
+    }
+
+    let artists = song.artists.into_iter()
+        .filter(|artist| !normalize(artist).is_empty())
+        .collect::<Vec<String>>();
+    if let Some(lyrics) = song.lyrics.as_ref() {
+        if make_blank(lyrics).is_none() {
+            return Err("빈칸을 만들 수 있는 가사 줄이 없습니다.".to_owned());
+        }
+    }
+    let lyrics = song.lyrics;
+    let kind = if lyrics.is_some() {
+        RoundKind::Lyrics
+    } else if song.thumbnail {
+        RoundKind::Thumbnail
+    } else if !artists.is_empty() {
+        RoundKind::TitleArtist
+    } else {
+        RoundKind::Standard
+    };
+
+    if song.start > MAX_CLIP_START {
+        return Err(format!("start는 {}초 이하여야 합니다.", MAX_CLIP_START));
+    }
+
+    let duration = song.duration.unwrap_or(DEFAULT_CLIP_DURATION);
+    if duration == 0 || duration > MAX_CLIP_DURATION {
+        return Err(format!("duration은 1~{}초 사이여야 합니다.", MAX_CLIP_DURATION));
+    }
+
+    let points = song.points.unwrap_or(1);
+    if points <= 0 {
+        return Err("points는 1 이상이어야 합니다.".to_owned());
+    }
+
+    Ok(QuizEntry {
+        url_id: None,
+        url,
+        answers,
+        start: song.start,
+        duration,
+        hints: song.hints,
+        points,
+        transform: song.transform,
+        artists,
+        lyrics,
+        kind,
+    })
+}
@@ -0,0 +1,569 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::Duration,
+};
+
+use serde::{Serialize, Deserialize};
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    model::{
+        id::{ChannelId, GuildId, UserId},
+        user::User,
+    },
+    prelude::RwLock,
+};
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    time::{sleep, Instant},
+};
+
+use crate::{
+    database_handler::{QuizSong, PlayerRecord, record_player_stats, record_song_stats, coop_high_score, set_coop_high_score},
+    quiz_handler::{
+        achievement::check_achievements,
+        challenge::submit_challenge_result,
+        clip::Transform,
+        game_code::save_game_code,
+        round::{run_standard_round, send_reveal, RoundKind, RoundResult, RoundScore},
+        scoring::{load_scoring_rules, ScoringRules},
+        season::current_season,
+        tag_draft::DRAFT_VOTE_TIME,
+        win_condition::{WinCondition, COOP_TIME_BONUS},
+    },
+    utils::guild_queue::GuildQueue,
+    DBContainer,
+    GuildQueueContainer,
+};
+
+pub const DEFAULT_ANSWER_TIME: u64 = 30;
+pub const DEFAULT_HINT_INTERVAL: u64 = 10;
+pub const DEFAULT_CLIP_DURATION: u64 = 20;
+const LOBBY_WAIT: u64 = 5;
+
+// 채팅으로 들어온 정답 시도
+pub struct Guess {
+    pub user: User,
+    pub channel_id: ChannelId,
+    pub content: String,
+    // 답 제출 modal로 들어온 답
+    pub private: bool,
+}
+
+// 퀴즈 한 문제
+#[derive(Clone, Debug)]
+pub struct QuizEntry {
+    // DB에 있는 곡이면 url 테이블의 id
+    pub url_id: Option<u64>,
+    // youtube video id
+    pub url: String,
+    pub answers: Vec<String>,
+    pub start: u64,
+    pub duration: u64,
+    pub hints: Vec<String>,
+    pub points: i64,
+    pub transform: Transform,
+    pub artists: Vec<String>,
+    // 가사 빈칸 라운드에 쓰는 전체 가사
+    pub lyrics: Option<String>,
+    pub kind: RoundKind,
+}
+
+impl QuizEntry {
+    pub fn from_song(song: QuizSong, start: u64, duration: u64, transform: Transform) -> QuizEntry {
+        QuizEntry {
+            url_id: Some(song.id),
+            url: song.url,
+            answers: song.titles,
+            artists: song.artists,
+            lyrics: song.lyrics,
+            start,
+            duration,
+            hints: vec![],
+            points: 1,
+            transform,
+            kind: RoundKind::Standard,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuizSettings {
+    // 한 라운드에서 정답을 받는 시간(초)
+    pub answer_time: u64,
+    // 힌트 공개 간격(초)
+    pub hint_interval: u64,
+    #[serde(default)]
+    pub win_condition: WinCondition,
+    // 채팅 대신 답 제출 버튼으로만 답을 받음
+    #[serde(default)]
+    pub private_answers: bool,
+    // 가사 빈칸 라운드에서도 구간을 재생
+    #[serde(default)]
+    pub lyric_audio: bool,
+    // 라운드 사이에 태그 투표로 다음 곡을 고름
+    #[serde(default)]
+    pub tag_draft: bool,
+}
+
+impl Default for QuizSettings {
+    fn default() -> Self {
+        QuizSettings {
+            answer_time: DEFAULT_ANSWER_TIME,
+            hint_interval: DEFAULT_HINT_INTERVAL,
+            win_condition: WinCondition::default(),
+            private_answers: false,
+            lyric_audio: false,
+            tag_draft: false,
+        }
+    }
+}
+
+pub struct PlayerScore {
+    pub name: String,
+    pub points: i64,
+}
+
+pub struct QuizSession {
+    pub gid: GuildId,
+    pub channel_id: ChannelId,
+    pub entries: Vec<QuizEntry>,
+    pub settings: QuizSettings,
+    pub scores: HashMap<UserId, PlayerScore>,
+    // 맞힌 곡 수
+    pub solved: usize,
+    // 호스트 진행 모드면 호스트의 id
+    pub host: Option<UserId>,
+    // 게임 코드로 불러온 퀴즈면 그 코드
+    pub code: Option<String>,
+    // 프로필에 반영할 개인 기록과 현재 연속 정답 수
+    pub records: HashMap<UserId, PlayerRecord>,
+    pub streaks: HashMap<UserId, i64>,
+    // 서버에 설정된 점수 규칙 (시작할 때 불러옴)
+    pub rules: ScoringRules,
+    // 협동 모드의 팀 점수와 끝난 뒤의 서버 최고 기록
+    pub team_score: i64,
+    pub coop_best: Option<i64>,
+    // 서버 대항전으로 플레이하면 도전 번호
+    pub challenge: Option<u64>,
+    // 출제된 DB 곡의 url id와 정답자가 있었는지
+    pub played: Vec<(u64, bool)>,
+}
+
+impl QuizSession {
+    pub fn new(gid: GuildId, channel_id: ChannelId, entries: Vec<QuizEntry>, settings: QuizSettings) -> QuizSession {
+        QuizSession {
+            gid,
+            channel_id,
+            entries,
+            settings,
+            scores: HashMap::new(),
+            solved: 0,
+            host: None,
+            code: None,
+            records: HashMap::new(),
+            streaks: HashMap::new(),
+            rules: ScoringRules::default(),
+            team_score: 0,
+            coop_best: None,
+            challenge: None,
+            played: vec![],
+        }
+    }
+
+    // 퀴즈를 끝까지 진행하고 최종 순위를 채널에 올림
+    // 타임어택이나 협동처럼 15분을 넘길 수 있는 퀴즈는 interaction 응답을 수정할 수 없으므로
+    // 시작하지 못했을 때만 응답할 embed를 반환
+    pub async fn run(&mut self, ctx: &Context) -> Option<CreateEmbed> {
+        let queue_lock = guild_queue_lock(ctx, self.gid).await;
+        let (guess_channel, mut receiver) = match open_guess_channel(&queue_lock).await {
+            Some(opened) => opened,
+            None => {
+                let mut embed = CreateEmbed::default();
+                embed.title("퀴즈를 시작할 수 없습니다.")
+                    .description("이미 진행 중인 퀴즈가 있습니다.");
+                return Some(embed);
+            }
+        };
+
+        // 업적 공지나 대항전 결과 발표처럼 오래 걸리는 작업 중에 data 잠금을 잡고 있지 않도록 연결을 복사해서 씀
+        let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+        // 대항전은 두 서버가 같은 규칙으로 겨뤄야 하므로 기본 규칙 사용
+        if self.challenge.is_none() {
+            self.rules = load_scoring_rules(&conn, self.gid.0).await;
+        }
+
+        let lobby = self.lobby_embed();
+        if let Err(why) = self.channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.clone_from(&lobby);
+                e
+            })
+        }).await {
+            println!("failed to send quiz lobby {:?}", why);
+        }
+        sleep(Duration::from_secs(LOBBY_WAIT)).await;
+
+        match self.host {
+            Some(host) => {
+                // 호스트가 직접 채점하므로 채팅 정답은 읽지 않음
+                // 정답이 쌓이지 않도록 받는 쪽은 닫고, 다른 퀴즈가 끼어들지 않도록 채널 자리는 계속 차지
+                receiver.close();
+                self.run_hosted(ctx, host).await
+            },
+            None => self.run_rounds(ctx, &mut receiver).await,
+        }
+
+        // 라운드가 끝났으니 결과를 정리하는 동안 다른 퀴즈를 시작할 수 있음
+        drop(guess_channel);
+
+        if self.code.is_none() {
+            let code = save_game_code(&conn, self).await;
+            self.code = code;
+        }
+        if !self.played.is_empty() {
+            let played = self.played.drain(..).collect();
+            if record_song_stats(&conn, played).await.is_err() {
+                println!("failed to record song stats");
+            }
+        }
+        // 호스트 진행 모드는 채점 기록이 없으므로 records가 비어 있음
+        if !self.records.is_empty() {
+            self.mark_winner();
+            let records = self.records.drain().map(|(_, record)| record).collect::<Vec<PlayerRecord>>();
+            let players = records.iter()
+                .map(|record| (record.user_id, record.name.to_owned()))
+                .collect();
+            match record_player_stats(&conn, self.gid.0, current_season(), records).await {
+                Ok(_) => check_achievements(ctx, &conn, self.channel_id, players).await,
+                Err(_) => println!("failed to record player stats"),
+            }
+        }
+        if let WinCondition::Coop { .. } = self.settings.win_condition {
+            let best = coop_high_score(&conn, self.gid.0).await;
+            if self.team_score > best.unwrap_or(0) {
+                if set_coop_high_score(&conn, self.gid.0, self.team_score).await.is_ok() {
+                    let previous = best.map_or("없음".to_owned(), |best| format!("{}점", best));
+                    if let Err(why) = self.channel_id.send_message(&ctx.http, |m| {
+                        m.embed(|e| {
+                            e.title("🎉 협동 최고 기록 경신!")
+                                .description(format!("{}점 (이전 기록: {})", self.team_score, previous))
+                        })
+                    }).await {
+                        println!("failed to announce coop high score {:?}", why);
+                    }
+                }
+            }
+            self.coop_best = Some(best.unwrap_or(0).max(self.team_score));
+        }
+        if let Some(id) = self.challenge {
+            submit_challenge_result(ctx, &conn, id, self).await;
+        }
+
+        let mut embed = self.scoreboard_embed();
+        if let Some(code) = self.code.as_ref() {
+            embed.footer(|f| f.text(format!("게임 코드: {} (/퀴즈시작 code:{})", code, code)));
+        }
+        if let Err(why) = self.channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.clone_from(&embed);
+                e
+            })
+        }).await {
+            println!("failed to send quiz results {:?}", why);
+        }
+        None
+    }
+
+    async fn run_rounds(&mut self, ctx: &Context, receiver: &mut UnboundedReceiver<Guess>) {
+        let mut game_deadline = self.settings.win_condition.deadline(Instant::now());
+        for idx in 0..self.entries.len() {
+            if self.is_finished(game_deadline) {
+                break;
+            }
+            // 첫 곡은 투표 없이 준비된 곡으로 시작
+            if self.settings.tag_draft && idx > 0 {
+                self.draft_next_song(ctx, idx).await;
+            }
+            let entry = self.entries[idx].clone();
+            let title = self.round_title(idx, game_deadline);
+            let result = run_standard_round(ctx, self, title, &entry, receiver, game_deadline).await;
+            if !result.solvers.is_empty() {
+                self.solved += 1;
+            }
+            if let Some(url_id) = entry.url_id {
+                self.played.push((url_id, !result.solvers.is_empty()));
+            }
+            self.record_round(&entry, &result);
+            let score = self.score_round(&entry, &result);
+            send_reveal(ctx, self, &entry, &result.reveal, &score).await;
+            // 협동 모드는 맞힌 만큼 전체 제한시간이 늘어남
+            if let WinCondition::Coop { .. } = self.settings.win_condition {
+                let bonus = Duration::from_secs(COOP_TIME_BONUS * result.solvers.len() as u64);
+                game_deadline = game_deadline.map(|deadline| deadline + bonus);
+            }
+        }
+    }
+
+    // 정답률은 답을 낸 라운드 기준, 연속 정답은 한 판 안에서만 셈
+    fn record_round(&mut self, entry: &QuizEntry, result: &RoundResult) {
+        for (user_id, name) in result.participants.iter() {
+            let record = self.records.entry(*user_id).or_insert(PlayerRecord {
+                user_id: user_id.0,
+                name: name.to_owned(),
+                rounds: 0,
+                correct: 0,
+                reaction_ms: 0,
+                best_streak: 0,
+                solved_urls: vec![],
+                fastest_ms: None,
+                won: false,
+            });
+            record.rounds += 1;
+        }
+
+        // 제목과 가수를 모두 맞혀도 한 라운드는 한 번만 셈
+        let mut solved_users = vec![];
+        for (user, reaction) in result.solvers.iter() {
+            if solved_users.contains(&user.id) {
+                continue;
+            }
+            if let Some(record) = self.records.get_mut(&user.id) {
+                record.correct += 1;
+                let reaction = reaction.as_millis() as i64;
+                record.reaction_ms += reaction;
+                record.fastest_ms = Some(record.fastest_ms.map_or(reaction, |fastest| fastest.min(reaction)));
+                if let Some(url_id) = entry.url_id {
+                    record.solved_urls.push(url_id);
+                }
+            }
+            solved_users.push(user.id);
+        }
+
+        for (user_id, streak) in self.streaks.iter_mut() {
+            if !solved_users.contains(user_id) {
+                *streak = 0;
+            }
+        }
+        for user_id in solved_users {
+            let streak = self.streaks.entry(user_id).or_insert(0);
+            *streak += 1;
+            if let Some(record) = self.records.get_mut(&user_id) {
+                record.best_streak = record.best_streak.max(*streak);
+            }
+        }
+    }
+
+    // 연속 정답 수는 record_round에서 갱신된 값을 사용
+    fn score_round(&mut self, entry: &QuizEntry, result: &RoundResult) -> RoundScore {
+        let mut awards = Vec::new();
+        for (user, reaction) in result.solvers.iter() {
+            let streak = self.streaks.get(&user.id).copied().unwrap_or(1);
+            let points = self.rules.points(entry.points, *reaction, streak);
+            self.award(user.id, &user.name, points);
+            self.team_score += points;
+            awards.push(points);
+        }
+        let mut penalties = Vec::new();
+        if self.rules.wrong_penalty > 0 {
+            let coop = matches!(self.settings.win_condition, WinCondition::Coop { .. });
+            for (user_id, count) in result.wrong.iter() {
+                let penalty = self.rules.wrong_penalty * count;
+                // 0점 아래로는 안 내려가므로 실제로 깎인 만큼만 알려줌
+                let mut lost = match self.scores.get_mut(user_id) {
+                    Some(score) => {
+                        let lost = score.points.min(penalty);
+                        score.points -= lost;
+                        lost
+                    },
+                    None => 0,
+                };
+                // 협동 모드는 팀 점수로 겨루므로 팀 점수에서도 깎음
+                if coop {
+                    lost = self.team_score.min(penalty);
+                    self.team_score -= lost;
+                }
+                if lost > 0 {
+                    let name = result.participants.get(user_id).cloned().unwrap_or_else(|| user_id.0.to_string());
+                    penalties.push((name, lost));
+                }
+            }
+        }
+        RoundScore { awards, penalties }
+    }
+
+    // 2명 이상 참여하고 단독 1등이면 우승
+    // 협동 모드는 개인 우승이 없음
+    fn mark_winner(&mut self) {
+        if let WinCondition::Coop { .. } = self.settings.win_condition {
+            return;
+        }
+        if self.records.len() < 2 {
+            return;
+        }
+        let ranking = self.scores.iter()
+            .map(|(user_id, score)| (*user_id, score.points))
+            .collect::<Vec<(UserId, i64)>>();
+        let best = match ranking.iter().map(|(_, points)| *points).max() {
+            Some(best) if best > 0 => best,
+            _ => return,
+        };
+        let leaders = ranking.iter()
+            .filter(|(_, points)| *points == best)
+            .collect::<Vec<&(UserId, i64)>>();
+        if let [(user_id, _)] = leaders.as_slice() {
+            if let Some(record) = self.records.get_mut(user_id) {
+                record.won = true;
+            }
+        }
+    }
+
+    fn is_finished(&self, game_deadline: Option<Instant>) -> bool {
+        match self.settings.win_condition {
+            WinCondition::FixedRounds => false,
+            WinCondition::FirstTo { points } => self.scores.values().any(|score| score.points >= points),
+            WinCondition::TimeAttack { .. } | WinCondition::Coop { .. } => game_deadline.map_or(false, |deadline| Instant::now() >= deadline),
+        }
+    }
+
+    fn round_title(&self, idx: usize, game_deadline: Option<Instant>) -> String {
+        match self.settings.win_condition {
+            WinCondition::FixedRounds => format!("{}/{} 라운드", idx + 1, self.entries.len()),
+            WinCondition::FirstTo { points } => format!("{} 라운드 (목표 {}점)", idx + 1, points),
+            WinCondition::TimeAttack { .. } => {
+                let remain = game_deadline
+                    .map(|deadline| deadline.saturating_duration_since(Instant::now()).as_secs())
+                    .unwrap_or(0);
+                format!("{} 라운드 (남은 시간 {:02}:{:02})", idx + 1, remain / 60, remain % 60)
+            },
+            WinCondition::Coop { .. } => {
+                let remain = game_deadline
+                    .map(|deadline| deadline.saturating_duration_since(Instant::now()).as_secs())
+                    .unwrap_or(0);
+                format!("{} 라운드 (팀 점수 {}점, 남은 시간 {:02}:{:02})", idx + 1, self.team_score, remain / 60, remain % 60)
+            },
+        }
+    }
+
+    pub fn lobby_embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        embed.title("퀴즈 대기실")
+            .description(format!("{}초 후 퀴즈가 시작됩니다.", LOBBY_WAIT));
+        match self.host {
+            Some(host) => {
+                embed.field("진행", format!("<@{}> 호스트 진행", host.0), true)
+                    .field("곡 수", format!("{}곡", self.entries.len()), true);
+            },
+            None => {
+                embed.field("종료 조건", self.settings.win_condition.description(self.entries.len()), true)
+                    .field("정답 시간", format!("{}초", self.settings.answer_time), true)
+                    .field("힌트 간격", format!("{}초", self.settings.hint_interval), true)
+                    .field("점수 규칙", self.rules.description(), false);
+                if self.settings.private_answers {
+                    embed.field("답 제출", "채팅 대신 답 제출 버튼 사용", false);
+                }
+                if self.entries.iter().any(|entry| entry.kind == RoundKind::Lyrics) {
+                    let audio = if self.settings.lyric_audio { "구간 재생" } else { "음악 없음" };
+                    embed.field("가사 빈칸", audio, false);
+                }
+                if self.entries.iter().any(|entry| entry.kind == RoundKind::Thumbnail) {
+                    embed.field("썸네일", "힌트 간격마다 더 선명해짐", false);
+                }
+                if self.settings.tag_draft {
+                    embed.field("태그 투표", format!("라운드 사이에 {}초 동안 다음 곡의 태그를 투표", DRAFT_VOTE_TIME), false);
+                }
+            },
+        }
+        embed
+    }
+
+    pub fn award(&mut self, user_id: UserId, name: &str, points: i64) {
+        let score = self.scores
+            .entry(user_id)
+            .or_insert(PlayerScore { name: name.to_owned(), points: 0 });
+        score.points += points;
+    }
+
+    pub fn ranking(&self) -> Vec<&PlayerScore> {
+        let mut ranking = self.scores.values().collect::<Vec<&PlayerScore>>();
+        ranking.sort_by(|a, b| b.points.cmp(&a.points));
+        ranking
+    }
+
+    pub fn scoreboard_embed(&self) -> CreateEmbed {
+        let ranking = self.ranking();
+        let description = if ranking.is_empty() {
+            "정답자가 없습니다.".to_owned()
+        } else {
+            ranking.iter()
+                .enumerate()
+                .map(|(idx, score)| format!("{}. {} - {}점", idx + 1, score.name, score.points))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+
+        let mut embed = CreateEmbed::default();
+        embed.title("퀴즈 결과")
+            .description(description)
+            .field("종료 조건", self.settings.win_condition.description(self.entries.len()), true);
+        match self.settings.win_condition {
+            WinCondition::TimeAttack { .. } => {
+                embed.field("맞힌 곡", format!("{}곡", self.solved), true);
+            },
+            WinCondition::Coop { .. } => {
+                embed.title("협동 결과")
+                    .field("팀 점수", format!("{}점", self.team_score), true)
+                    .field("맞힌 곡", format!("{}곡", self.solved), true);
+                if let Some(best) = self.coop_best {
+                    embed.field("서버 최고 기록", format!("{}점", best), true);
+                }
+            },
+            _ => (),
+        }
+        embed
+    }
+}
+
+pub async fn guild_queue_lock(ctx: &Context, gid: GuildId) -> Arc<RwLock<GuildQueue>> {
+    let data = ctx.data.read().await;
+    let data = data.get::<GuildQueueContainer>().unwrap();
+    data.get(&gid).unwrap().clone()
+}
+
+// 열어 둔 정답 채널, drop되면 닫힘
+// 퀴즈 태스크가 도중에 패닉해도 길드가 다음 퀴즈를 시작할 수 있도록 drop에서 정리
+pub(crate) struct GuessChannel {
+    queue_lock: Arc<RwLock<GuildQueue>>,
+    sender: UnboundedSender<Guess>,
+}
+
+impl Drop for GuessChannel {
+    fn drop(&mut self) {
+        let queue_lock = self.queue_lock.clone();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let mut queue = queue_lock.write().await;
+            // 그 사이 새로 시작한 퀴즈의 채널은 건드리지 않음
+            if queue.guess_sender.as_ref().map_or(false, |current| current.same_channel(&sender)) {
+                queue.guess_sender = None;
+            }
+        });
+    }
+}
+
+// 길드당 하나의 퀴즈만 정답을 받을 수 있음
+// 채팅 정답을 받지 않는 모드도 다른 퀴즈와 겹치지 않도록 채널을 열어 자리를 차지함
+pub(crate) async fn open_guess_channel(queue_lock: &Arc<RwLock<GuildQueue>>) -> Option<(GuessChannel, UnboundedReceiver<Guess>)> {
+    let mut queue = queue_lock.write().await;
+    if queue.guess_sender.is_some() {
+        return None;
+    }
+    let (sender, receiver) = unbounded_channel();
+    queue.guess_sender = Some(sender.clone());
+    let guess_channel = GuessChannel {
+        queue_lock: queue_lock.clone(),
+        sender,
+    };
+    Some((guess_channel, receiver))
+}
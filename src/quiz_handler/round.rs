@@ -0,0 +1,416 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    time::Duration,
+};
+
+use serde::{Serialize, Deserialize};
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    model::{
+        application::component::ButtonStyle,
+        channel::AttachmentType,
+        id::UserId,
+        user::User,
+    },
+};
+use tokio::{
+    sync::mpsc::UnboundedReceiver,
+    task::spawn_blocking,
+    time::{timeout_at, Instant},
+};
+
+use crate::quiz_handler::{
+    answer::is_correct,
+    clip::{play_clip, Transform},
+    lyric::{make_blank, LyricBlank},
+    private_answer::ANSWER_BUTTON_ID,
+    quiz_session::{Guess, QuizEntry, QuizSession},
+};
+use crate::utils::image_module::{
+    thumbnail::thumbnail,
+    pixelate::pixelated_stages,
+};
+use crate::DBContainer;
+
+const PICTURE_FILE: &str = "thumbnail.png";
+
+// 라운드 종류
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundKind {
+    // 제목만 맞히기
+    #[default]
+    Standard,
+    // 제목과 가수를 따로 맞히기
+    TitleArtist,
+    // 가사 한 줄의 빈칸 맞히기
+    Lyrics,
+    // 모자이크된 썸네일 보고 맞히기
+    Thumbnail,
+}
+
+// 한 라운드에서 맞혀야 하는 대상
+struct Target<'a> {
+    name: &'static str,
+    answers: &'a [String],
+    solver: Option<User>,
+    // 재생 시작부터 맞힐 때까지 걸린 시간
+    reaction: Duration,
+}
+
+// 라운드 결과
+pub struct RoundResult {
+    // 대상별로 처음 맞힌 사람과 걸린 시간
+    pub solvers: Vec<(User, Duration)>,
+    // 답을 한 번이라도 낸 사람
+    pub participants: HashMap<UserId, String>,
+    // 사람별 틀린 답 수
+    pub wrong: HashMap<UserId, i64>,
+    // 점수를 매긴 뒤 send_reveal로 보낼 정답 공개 내용
+    pub reveal: RoundReveal,
+}
+
+pub struct RoundReveal {
+    // 대상 이름, 대표 정답, 맞힌 사람 이름
+    targets: Vec<(&'static str, String, Option<String>)>,
+    // 가사 라운드면 어떤 곡이었는지도 알려줌
+    lyric_round: bool,
+    // 비공개 답 모드에서 사람마다 마지막으로 제출한 답과 정답 여부
+    submissions: Vec<(String, String, bool)>,
+    // 썸네일 라운드의 원본 그림
+    original: Option<Vec<u8>>,
+}
+
+// score_round가 실제로 반영한 점수
+pub struct RoundScore {
+    // RoundResult.solvers와 같은 순서로 맞힌 사람이 받은 점수
+    pub awards: Vec<i64>,
+    // 오답으로 점수가 깎인 사람의 이름과 깎인 점수
+    pub penalties: Vec<(String, i64)>,
+}
+
+fn round_targets<'a>(entry: &'a QuizEntry, blank: Option<&'a LyricBlank>) -> Vec<Target<'a>> {
+    if let Some(blank) = blank {
+        return vec![Target { name: "가사", answers: &blank.answers, solver: None, reaction: Duration::ZERO }];
+    }
+    let mut targets = vec![Target { name: "제목", answers: &entry.answers, solver: None, reaction: Duration::ZERO }];
+    if entry.kind == RoundKind::TitleArtist && !entry.artists.is_empty() {
+        targets.push(Target { name: "가수", answers: &entry.artists, solver: None, reaction: Duration::ZERO });
+    }
+    targets
+}
+
+// 일반 라운드
+// 구간을 재생하고 대상별로 처음 맞힌 사람과 참여자를 반환
+// 모든 대상을 맞히거나 시간이 끝나면 종료
+pub async fn run_standard_round(
+    ctx: &Context,
+    session: &QuizSession,
+    title: String,
+    entry: &QuizEntry,
+    receiver: &mut UnboundedReceiver<Guess>,
+    game_deadline: Option<Instant>,
+) -> RoundResult {
+
+    // 이전 라운드가 끝난 뒤 들어온 답은 버림
+    while receiver.try_recv().is_ok() {}
+
+    let private = session.settings.private_answers;
+    // 가사가 없거나 빈칸을 만들 줄이 없으면 일반 라운드로 진행
+    let blank = match entry.kind {
+        RoundKind::Lyrics => entry.lyrics.as_deref().and_then(make_blank),
+        _ => None,
+    };
+    // 썸네일을 못 받으면 일반 라운드로 진행
+    // 마지막 단계는 원본이라 정답 공개 때만 보여줌
+    let stages = match entry.kind {
+        RoundKind::Thumbnail => {
+            let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+            match thumbnail(&conn, &entry.url).await {
+                // 이미지 처리는 오래 걸려 다른 서버의 이벤트를 막지 않도록 블로킹 스레드에서 함
+                Some(raw) => spawn_blocking(move || pixelated_stages(&raw)).await.ok().flatten(),
+                None => None,
+            }
+        },
+        _ => None,
+    };
+    let mut targets = round_targets(entry, blank.as_ref());
+    // 비공개 답 모드에서 사람마다 마지막으로 제출한 답
+    let mut submissions: HashMap<UserId, (String, String)> = HashMap::new();
+    let mut participants = HashMap::new();
+    let mut wrong = HashMap::new();
+    let mut revealed = 0;
+    let mut embed = round_embed(&title, entry, &targets, blank.as_ref(), revealed, session.settings.answer_time, private);
+    // 메시지를 못 보내도 라운드는 계속 진행 (정답 채널이 열린 채로 태스크가 죽지 않도록)
+    let mut msg = match session.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| {
+            e.clone_from(&embed);
+            e
+        });
+        if let Some(stages) = stages.as_ref() {
+            m.add_file(picture_attachment(&stages[0]));
+        }
+        if private {
+            m.components(|c| {
+                c.create_action_row(|row| {
+                    row.create_button(|b| b.custom_id(ANSWER_BUTTON_ID).label("답 제출").style(ButtonStyle::Primary))
+                })
+            });
+        }
+        m
+    }).await {
+        Ok(msg) => Some(msg),
+        Err(why) => {
+            println!("failed to send round message {:?}", why);
+            None
+        },
+    };
+
+    // 가사 라운드는 설정에 따라, 썸네일 라운드는 항상 음악 없이 진행
+    let play_audio = match (blank.as_ref(), stages.as_ref()) {
+        (Some(_), _) => session.settings.lyric_audio,
+        (None, Some(_)) => false,
+        (None, None) => true,
+    };
+    let handle = if play_audio {
+        match play_clip(ctx, session.gid, entry.url.to_owned(), entry.start, entry.duration, entry.transform).await {
+            Ok(handle) => Some(handle),
+            Err(why) => {
+                println!("quiz clip error {:?}", why);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let started = Instant::now();
+    let mut deadline = started + Duration::from_secs(session.settings.answer_time);
+    // 타임어택은 전체 제한시간이 지나면 라운드도 끝남
+    if let Some(game_deadline) = game_deadline {
+        deadline = deadline.min(game_deadline);
+    }
+    let hint_interval = Duration::from_secs(session.settings.hint_interval.max(1));
+    let mut next_hint = started + hint_interval;
+    // 썸네일 라운드는 힌트 간격마다 더 선명한 그림을 올림
+    let hint_count = match stages.as_ref() {
+        Some(stages) => entry.hints.len().max(stages.len() - 2),
+        None => entry.hints.len(),
+    };
+
+    loop {
+        let next_event = if revealed < hint_count && next_hint < deadline {
+            next_hint
+        } else {
+            deadline
+        };
+
+        match timeout_at(next_event, receiver.recv()).await {
+            Ok(Some(guess)) => {
+                if guess.channel_id != session.channel_id || guess.private != private {
+                    continue;
+                }
+                participants.insert(guess.user.id, guess.user.name.to_owned());
+                if private {
+                    submissions.insert(guess.user.id, (guess.user.name.to_owned(), guess.content.to_owned()));
+                }
+                let solved = targets.iter_mut()
+                    .find(|target| target.solver.is_none() && is_correct(&guess.content, target.answers));
+                match solved {
+                    Some(target) => {
+                        target.solver = Some(guess.user);
+                        target.reaction = started.elapsed();
+                    },
+                    None => {
+                        // 이미 누가 맞힌 대상의 정답은 틀린 답으로 치지 않음
+                        if !targets.iter().any(|target| is_correct(&guess.content, target.answers)) {
+                            *wrong.entry(guess.user.id).or_insert(0) += 1;
+                        }
+                        continue;
+                    },
+                }
+                if targets.iter().all(|target| target.solver.is_some()) {
+                    break;
+                }
+            },
+            Ok(None) => break,
+            Err(_) => {
+                if next_event >= deadline {
+                    break;
+                }
+                revealed += 1;
+                next_hint += hint_interval;
+                if let Some(stages) = stages.as_ref() {
+                    if revealed < stages.len() - 1 {
+                        send_picture(ctx, session, &stages[revealed], "더 선명한 썸네일").await;
+                    }
+                }
+            },
+        }
+
+        let remain = deadline.saturating_duration_since(Instant::now()).as_secs();
+        embed = round_embed(&title, entry, &targets, blank.as_ref(), revealed, remain, private);
+        if let Some(msg) = msg.as_mut() {
+            if let Err(why) = msg.edit(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.clone_from(&embed);
+                    e
+                })
+            }).await {
+                println!("failed to update round message {:?}", why);
+            }
+        }
+    }
+
+    if let Some(handle) = handle {
+        let _ = handle.stop();
+    }
+    if let (true, Some(msg)) = (private, msg.as_mut()) {
+        if let Err(why) = msg.edit(&ctx.http, |m| m.components(|c| c)).await {
+            println!("failed to remove answer button {:?}", why);
+        }
+    }
+
+    let reveal = RoundReveal {
+        targets: targets.iter()
+            .map(|target| (
+                target.name,
+                target.answers.first().cloned().unwrap_or_default(),
+                target.solver.as_ref().map(|user| user.name.to_owned()),
+            ))
+            .collect(),
+        lyric_round: blank.is_some(),
+        submissions: submissions.into_values()
+            .map(|(name, content)| {
+                let correct = targets.iter().any(|target| is_correct(&content, target.answers));
+                (name, content, correct)
+            })
+            .collect(),
+        original: stages.and_then(|mut stages| stages.pop()),
+    };
+
+    let solvers = targets.into_iter()
+        .filter_map(|target| target.solver.map(|user| (user, target.reaction)))
+        .collect();
+    RoundResult {
+        solvers,
+        participants,
+        wrong,
+        reveal,
+    }
+}
+
+// 점수를 매긴 뒤 정답과 실제로 받은 점수를 공개
+pub async fn send_reveal(ctx: &Context, session: &QuizSession, entry: &QuizEntry, reveal: &RoundReveal, score: &RoundScore) {
+    let result = reveal_embed(entry, reveal, score);
+    if let Err(why) = session.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| {
+            e.clone_from(&result);
+            if reveal.original.is_some() {
+                e.attachment(PICTURE_FILE);
+            }
+            e
+        });
+        if let Some(original) = reveal.original.as_ref() {
+            m.add_file(picture_attachment(original));
+        }
+        m
+    }).await {
+        println!("failed to send round reveal {:?}", why);
+    }
+}
+
+fn picture_attachment(data: &[u8]) -> AttachmentType<'_> {
+    AttachmentType::Bytes {
+        data: Cow::Borrowed(data),
+        filename: PICTURE_FILE.to_owned(),
+    }
+}
+
+async fn send_picture(ctx: &Context, session: &QuizSession, data: &[u8], title: &str) {
+    if let Err(why) = session.channel_id.send_message(&ctx.http, |m| {
+        m.embed(|e| e.title(title).attachment(PICTURE_FILE))
+            .add_file(picture_attachment(data))
+    }).await {
+        println!("failed to send picture {:?}", why);
+    }
+}
+
+fn round_embed(title: &str, entry: &QuizEntry, targets: &[Target], blank: Option<&LyricBlank>, revealed: usize, remain: u64, private: bool) -> CreateEmbed {
+    let mut embed = CreateEmbed::default();
+    let names = targets.iter()
+        .map(|target| target.name)
+        .collect::<Vec<&str>>()
+        .join(", ");
+    let how = if private { "답 제출 버튼으로" } else { "채팅으로" };
+    embed.title(title)
+        .description(format!("{} {}을 입력해주세요. ({}초)", how, names, remain));
+    if let Some(blank) = blank {
+        embed.field("빈칸 채우기", &blank.line, false);
+    }
+    // 대상이 여럿이면 아직 못 맞힌 대상을 표시
+    if targets.len() > 1 {
+        for target in targets.iter() {
+            let state = match target.solver.as_ref() {
+                Some(user) => format!("✅ {}", user.name),
+                None => "❓".to_owned(),
+            };
+            embed.field(target.name, state, true);
+        }
+    }
+    if entry.transform != Transform::Original {
+        embed.field("변형", entry.transform.name(), true);
+    }
+    if revealed > 0 && !entry.hints.is_empty() {
+        let hints = entry.hints.iter()
+            .take(revealed)
+            .enumerate()
+            .map(|(idx, hint)| format!("{}. {}", idx + 1, hint))
+            .collect::<Vec<String>>()
+            .join("\n");
+        embed.field("힌트", hints, false);
+    }
+    embed
+}
+
+fn reveal_embed(entry: &QuizEntry, reveal: &RoundReveal, score: &RoundScore) -> CreateEmbed {
+    let mut embed = CreateEmbed::default();
+    if reveal.targets.iter().any(|(_, _, solver)| solver.is_some()) {
+        embed.title("정답!");
+    } else {
+        embed.title("시간 초과");
+    }
+    // 맞힌 대상 순서가 awards 순서와 같음
+    let mut awards = score.awards.iter();
+    for (name, answer, solver) in reveal.targets.iter() {
+        let solver = match solver.as_ref() {
+            Some(solver) => match awards.next() {
+                Some(points) => format!("{} (+{}점)", solver, points),
+                None => solver.to_owned(),
+            },
+            None => "정답자 없음".to_owned(),
+        };
+        embed.field(*name, format!("{}\n{}", answer, solver), true);
+    }
+    if reveal.lyric_round {
+        let title = entry.answers.first().map(String::as_str).unwrap_or_default();
+        embed.field("곡", title, true);
+    }
+    if !reveal.submissions.is_empty() {
+        let answers = reveal.submissions.iter()
+            .map(|(name, content, correct)| format!("{} {}: {}", if *correct { "✅" } else { "❌" }, name, content))
+            .collect::<Vec<String>>()
+            .join("\n");
+        embed.field("제출한 답", answers, false);
+    }
+    if !score.penalties.is_empty() {
+        let penalties = score.penalties.iter()
+            .map(|(name, points)| format!("{} -{}점", name, points))
+            .collect::<Vec<String>>()
+            .join("\n");
+        embed.field("오답 감점", penalties, false);
+    }
+    embed
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+use tokio_rusqlite::Connection as Connection;
+
+use crate::database_handler::*;
+
+// 서버별 점수 규칙
+// scoring_rule 테이블의 data 컬럼에 JSON으로 저장됨
+// 기본값은 정답당 1점으로 보너스와 감점이 없음
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringRules {
+    // 정답 점수 (퀴즈팩 곡별 points에 곱함)
+    pub correct_points: i64,
+    // 재생 직후 맞히면 받는 최대 보너스
+    pub speed_bonus: i64,
+    // 보너스가 0이 될 때까지의 시간(초), 이 안에서 선형으로 줄어듦
+    pub speed_window: u64,
+    // 연속 정답 한 번마다 늘어나는 배율(%)
+    pub streak_bonus: i64,
+    // 연속 정답 배율의 최대치(%)
+    pub streak_cap: i64,
+    // 틀린 답 하나당 감점 (0점 아래로는 내려가지 않음)
+    pub wrong_penalty: i64,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules {
+            correct_points: 1,
+            speed_bonus: 0,
+            speed_window: 10,
+            streak_bonus: 0,
+            streak_cap: 100,
+            wrong_penalty: 0,
+        }
+    }
+}
+
+impl ScoringRules {
+    // streak은 이번 라운드를 포함한 연속 정답 수
+    pub fn points(&self, base: i64, reaction: Duration, streak: i64) -> i64 {
+        let window = Duration::from_secs(self.speed_window.max(1));
+        let speed = if reaction < window {
+            let remain = (window - reaction).as_millis() as i64;
+            self.speed_bonus * remain / window.as_millis() as i64
+        } else {
+            0
+        };
+        let multiplier = 100 + (self.streak_bonus * (streak - 1).max(0)).min(self.streak_cap);
+        (base * self.correct_points + speed) * multiplier / 100
+    }
+
+    pub fn description(&self) -> String {
+        let mut lines = vec![format!("정답 {}점", self.correct_points)];
+        if self.speed_bonus > 0 {
+            lines.push(format!("속도 보너스 최대 {}점 ({}초 안에)", self.speed_bonus, self.speed_window));
+        }
+        if self.streak_bonus > 0 {
+            lines.push(format!("연속 정답마다 +{}% (최대 +{}%)", self.streak_bonus, self.streak_cap));
+        }
+        if self.wrong_penalty > 0 {
+            lines.push(format!("오답 -{}점 (0점 아래로는 안 내려감)", self.wrong_penalty));
+        }
+        lines.join("\n")
+    }
+}
+
+// 설정이 없거나 손상됐으면 기본 규칙
+pub async fn load_scoring_rules(conn: &Connection, guild_id: u64) -> ScoringRules {
+    match scoring_rule_data(conn, guild_id).await {
+        Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
+        Err(_) => ScoringRules::default(),
+    }
+}
+
+pub async fn save_scoring_rules(conn: &Connection, guild_id: u64, rules: &ScoringRules) -> Result<(), DBError> {
+    set_scoring_rule_data(conn, guild_id, serde_json::to_string(rules).unwrap()).await
+}
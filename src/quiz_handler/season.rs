@@ -0,0 +1,100 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use chrono::Utc;
+use chrono_tz::Asia::Seoul;
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    model::id::ChannelId,
+};
+use tokio::time::sleep;
+
+use crate::{
+    database_handler::*,
+    DBContainer,
+};
+
+// 시즌 종료 확인 간격(초)
+const SEASON_CHECK_INTERVAL: u64 = 60;
+pub const SEASON_RANKING_SIZE: usize = 20;
+
+// ready는 재접속할 때마다 다시 불리므로 작업은 한 번만 띄움
+static SEASON_TASK_STARTED: AtomicBool = AtomicBool::new(false);
+
+// 시즌은 한국 시간 기준 한 달 (YYYY-MM)
+pub fn current_season() -> String {
+    Utc::now().with_timezone(&Seoul).format("%Y-%m").to_string()
+}
+
+pub fn spawn_season_task(ctx: &Context) {
+    if SEASON_TASK_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        loop {
+            close_finished_seasons(&ctx).await;
+            sleep(Duration::from_secs(SEASON_CHECK_INTERVAL)).await;
+        }
+    });
+}
+
+// 지난 시즌 기록이 남아 있으면 마감하고 서버별로 최종 순위를 공지
+async fn close_finished_seasons(ctx: &Context) {
+    // 순위를 보내는 동안 data 잠금을 잡고 있지 않도록 연결을 복사해서 씀
+    let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+
+    let seasons = match unclosed_seasons(&conn, current_season()).await {
+        Ok(seasons) => seasons,
+        Err(_) => return,
+    };
+
+    for season in seasons {
+        let standings = match close_season(&conn, season.to_owned(), SEASON_RANKING_SIZE).await {
+            Ok(standings) => standings,
+            Err(_) => {
+                println!("failed to close season {}", season);
+                continue;
+            }
+        };
+        for standing in standings {
+            let channel_id = match standing.channel_id {
+                Some(channel_id) => ChannelId(channel_id),
+                None => continue,
+            };
+            let embed = season_embed(&season, &standing.ranking, true);
+            if let Err(why) = channel_id.send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.clone_from(&embed);
+                    e
+                })
+            }).await {
+                println!("failed to post season standings {:?}", why);
+            }
+        }
+    }
+}
+
+pub fn season_embed(season: &str, ranking: &[RankingRow], closed: bool) -> CreateEmbed {
+    let description = if ranking.is_empty() {
+        "기록이 없습니다.".to_owned()
+    } else {
+        ranking.iter()
+            .enumerate()
+            .map(|(idx, row)| format!("{}. {} - {}곡", idx + 1, row.name, row.correct))
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    let mut embed = CreateEmbed::default();
+    if closed {
+        embed.title(format!("{} 시즌 최종 순위", season));
+    } else {
+        embed.title(format!("{} 시즌 순위 (진행 중)", season));
+    }
+    embed.description(description);
+    embed
+}
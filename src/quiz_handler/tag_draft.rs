@@ -0,0 +1,145 @@
+use std::{
+    collections::HashMap,
+    time::Duration,
+};
+
+use futures::StreamExt;
+use rand::seq::SliceRandom;
+use serenity::{
+    client::Context,
+    model::{
+        application::interaction::InteractionResponseType,
+        id::UserId,
+    },
+};
+use tokio::time::{timeout, Instant};
+
+use crate::{
+    database_handler::*,
+    quiz_handler::{
+        quiz_session::{QuizEntry, QuizSession, DEFAULT_CLIP_DURATION},
+        round::RoundKind,
+    },
+    DBContainer,
+};
+
+// 투표를 받는 시간(초)
+pub const DRAFT_VOTE_TIME: u64 = 10;
+const DRAFT_TAG_COUNT: usize = 3;
+const DRAFT_MENU_ID: &str = "tag_draft";
+
+impl QuizSession {
+    // 라운드 사이에 태그 투표를 열고 idx번째 곡을 이긴 태그의 곡으로 바꿈
+    // 태그가 2개 미만이거나 곡을 못 찾으면 원래 곡을 그대로 출제
+    pub async fn draft_next_song(&mut self, ctx: &Context, idx: usize) {
+        // 투표를 받는 동안 data 잠금을 잡고 있지 않도록 연결을 복사해서 씀
+        let conn = ctx.data.read().await.get::<DBContainer>().unwrap().clone();
+        let tags = random_tags(&conn, self.gid.0, DRAFT_TAG_COUNT).await.unwrap_or_default();
+        if tags.len() < 2 {
+            return;
+        }
+
+        let mut msg = match self.channel_id.send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title("다음 곡 카테고리 투표")
+                    .description(format!("{}초 안에 다음 곡의 태그를 골라주세요.", DRAFT_VOTE_TIME))
+            })
+            .components(|c| {
+                c.create_action_row(|row| {
+                    row.create_select_menu(|menu| {
+                        menu.custom_id(DRAFT_MENU_ID)
+                            .placeholder("태그 선택")
+                            .options(|opts| {
+                                for tag in tags.iter() {
+                                    opts.create_option(|opt| opt.label(tag).value(tag));
+                                }
+                                opts
+                            })
+                    })
+                })
+            })
+        }).await {
+            Ok(msg) => msg,
+            Err(why) => {
+                // 투표를 못 받으면 준비된 곡을 그대로 출제
+                println!("failed to send tag vote {:?}", why);
+                return;
+            },
+        };
+
+        // 한 사람은 한 표, 다시 고르면 마지막 선택으로 바뀜
+        let mut votes: HashMap<UserId, String> = HashMap::new();
+        let mut collector = msg.await_component_interactions(ctx).build();
+        let deadline = Instant::now() + Duration::from_secs(DRAFT_VOTE_TIME);
+        loop {
+            let remain = deadline.saturating_duration_since(Instant::now());
+            let interaction = match timeout(remain, collector.next()).await {
+                Ok(Some(interaction)) => interaction,
+                _ => break,
+            };
+            // 후보에 없는 값이어도 응답은 보내야 discord가 상호작용 실패로 표시하지 않음
+            let content = match interaction.data.values.first() {
+                Some(tag) if tags.contains(tag) => {
+                    votes.insert(interaction.user.id, tag.to_owned());
+                    format!("{}에 투표했습니다.", tag)
+                },
+                _ => "후보에 없는 태그입니다.".to_owned(),
+            };
+            if let Err(why) = interaction.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| d.content(&content).ephemeral(true))
+            }).await {
+                println!("failed to respond tag vote {:?}", why);
+            }
+        }
+
+        let counts = tags.iter()
+            .map(|tag| (tag.to_owned(), votes.values().filter(|vote| *vote == tag).count()))
+            .collect::<Vec<(String, usize)>>();
+        // 동점이면 무작위, 아무도 투표하지 않으면 전체에서 무작위
+        let best = counts.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        let leaders = counts.iter()
+            .filter(|(_, count)| *count == best)
+            .map(|(tag, _)| tag.to_owned())
+            .collect::<Vec<String>>();
+        let winner = leaders.choose(&mut rand::thread_rng()).unwrap().to_owned();
+
+        let used = self.entries.iter().filter_map(|entry| entry.url_id).collect();
+        let song = random_tagged_song(&conn, self.gid.0, winner.to_owned(), used).await;
+        let result = counts.iter()
+            .map(|(tag, count)| format!("{} - {}표", tag, count))
+            .collect::<Vec<String>>()
+            .join("\n");
+        let picked = match song {
+            Ok(song) => {
+                self.replace_entry(idx, song);
+                format!("다음 곡은 **{}** 태그에서 나옵니다.", winner)
+            },
+            Err(_) => format!("**{}** 태그에 남은 곡이 없어 원래 곡을 출제합니다.", winner),
+        };
+
+        if let Err(why) = msg.edit(&ctx.http, |m| {
+            m.embed(|e| e.title("다음 곡 카테고리 투표 결과").description(format!("{}\n\n{}", result, picked)))
+                .components(|c| c)
+        }).await {
+            println!("failed to send tag vote result {:?}", why);
+        }
+    }
+
+    // 구간과 변형은 원래 문제를 따르고, 새 곡에 없는 라운드 종류는 일반 라운드로 바꿈
+    // 새 곡에 정해 둔 구간이 있으면 그 구간을 씀
+    fn replace_entry(&mut self, idx: usize, song: QuizSong) {
+        let old = &self.entries[idx];
+        let (start, duration) = match song.clip.as_ref() {
+            Some(clip) => (clip.start, clip.duration.unwrap_or(DEFAULT_CLIP_DURATION)),
+            None => (old.start, old.duration),
+        };
+        let mut entry = QuizEntry::from_song(song, start, duration, old.transform);
+        entry.kind = match old.kind {
+            RoundKind::Lyrics if entry.lyrics.is_none() => RoundKind::Standard,
+            RoundKind::TitleArtist if entry.artists.is_empty() => RoundKind::Standard,
+            kind => kind,
+        };
+        self.entries[idx] = entry;
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use serde::{Serialize, Deserialize};
+use tokio::time::Instant;
+
+pub const DEFAULT_TARGET_POINTS: i64 = 5;
+pub const DEFAULT_TIME_LIMIT: u64 = 5;
+// 협동 모드에서 정답 하나당 늘어나는 시간(초)
+pub const COOP_TIME_BONUS: u64 = 10;
+
+// 퀴즈 종료 조건
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WinCondition {
+    // 준비된 곡을 모두 출제
+    #[default]
+    FixedRounds,
+    // 누군가 points점에 먼저 도달하면 종료
+    FirstTo { points: i64 },
+    // minutes분 동안 최대한 많이 맞히기
+    TimeAttack { minutes: u64 },
+    // 서버 전체가 한 팀으로 점수를 모음, 정답마다 제한시간이 늘어남
+    Coop { minutes: u64 },
+}
+
+impl WinCondition {
+    // 명령어의 승리조건 선택지와 목표값으로 생성
+    pub fn from_option(kind: &str, goal: Option<i64>) -> Option<WinCondition> {
+        match kind {
+            "rounds" => Some(WinCondition::FixedRounds),
+            "first_to" => Some(WinCondition::FirstTo {
+                points: goal.unwrap_or(DEFAULT_TARGET_POINTS).max(1),
+            }),
+            "time_attack" => Some(WinCondition::TimeAttack {
+                minutes: goal.unwrap_or(DEFAULT_TIME_LIMIT as i64).max(1) as u64,
+            }),
+            "coop" => Some(WinCondition::Coop {
+                minutes: goal.unwrap_or(DEFAULT_TIME_LIMIT as i64).max(1) as u64,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn deadline(&self, started: Instant) -> Option<Instant> {
+        match self {
+            WinCondition::TimeAttack { minutes } | WinCondition::Coop { minutes } => Some(started + Duration::from_secs(minutes * 60)),
+            _ => None,
+        }
+    }
+
+    pub fn description(&self, rounds: usize) -> String {
+        match self {
+            WinCondition::FixedRounds => format!("{}라운드", rounds),
+            WinCondition::FirstTo { points } => format!("{}점 먼저 달성", points),
+            WinCondition::TimeAttack { minutes } => format!("타임어택 {}분", minutes),
+            WinCondition::Coop { minutes } => format!("협동 {}분 (정답마다 +{}초)", minutes, COOP_TIME_BONUS),
+        }
+    }
+}
@@ -9,6 +9,7 @@ use crate::utils::{
 };
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tokio_rusqlite::Connection;
 
 pub struct TrackEndNotifier {
     pub http: Arc<Http>,
@@ -37,6 +38,8 @@ pub struct TrackQueuingNotifier {
     pub http: Arc<Http>,
     pub guild_queue: Arc<RwLock<GuildQueue>>,
     pub voice_manager: Arc<Mutex<Call>>,
+    // 다음 곡 메타데이터를 읽을 DB
+    pub conn: Connection,
 }
 
 #[async_trait]
@@ -50,7 +53,7 @@ impl VoiceEventHandler for TrackQueuingNotifier {
                 tokio::join!(self.guild_queue.write(), self.voice_manager.lock());
             if !guild_queue.url_queue.is_empty() {
                 let url = guild_queue.url_queue.pop_front().unwrap();
-                let input = ytdl_optioned(url, 10, 30).await.unwrap();
+                let input = ytdl_optioned(&self.conn, url, 10, 30).await.unwrap();
                 let handle = voice_manager.play_source(input);
                 guild_queue.now_playing = Some(Arc::new(handle));
             } else {
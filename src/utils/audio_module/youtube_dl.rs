@@ -1,6 +1,5 @@
 use serde_json::Value;
 use std::{
-    fs::File,
     io::{BufRead, BufReader, Read, Cursor},
     process::{Stdio, Command},
     path::Path,
@@ -11,10 +10,6 @@ use tokio::{
         Command as TokioCommand,
     },
     task,
-    io::{
-        AsyncWriteExt,
-    },
-    fs::File as TokioFile,
 };
 use songbird::input::{
     Reader,
@@ -26,9 +21,11 @@ use songbird::input::{
     Metadata as SongbirdMetadata,
 };
 use ogg::PacketReader;
+use tokio_rusqlite::Connection;
 
 use crate::utils::audio_module::metadata::Metadata;
 use crate::utils::audio_module::dca;
+use crate::database_handler::{track_metadata, set_track_metadata};
 
 const YOUTUBE_DL_COMMAND: &str = "yt-dlp";
 const YTDL_COMMON_ARGS: [&str; 11] = [
@@ -59,25 +56,38 @@ const FFMPEG_ARGS: [&str; 10] = [
     "opus"
 ];
 
+// 재생하지 않고 메타데이터만 받을 때
+const YTDL_METADATA_ARGS: [&str; 5] = [
+    "-j",
+    "--skip-download",
+    "--no-playlist",
+    "--ignore-config",
+    "--no-warnings",
+];
+
 const TMP_FORLDER: &str = "./tmp/";
 
-pub async fn ytdl(url: impl AsRef<str>) -> Result<Input> {
-    ytdl_optioned(url, 0, 0).await
+pub async fn ytdl(conn: &Connection, url: impl AsRef<str>) -> Result<Input> {
+    ytdl_optioned(conn, url, 0, 0).await
+}
+
+pub async fn ytdl_optioned(conn: &Connection, url: impl AsRef<str>, start: u64, duration: u64) -> Result<Input> {
+    ytdl_filtered(conn, url, start, duration, None).await
 }
 
-pub async fn ytdl_optioned(url: impl AsRef<str>, mut start: u64, mut duration: u64) -> Result<Input> {
+// filter는 ffmpeg 오디오 필터 (-af)
+pub async fn ytdl_filtered(conn: &Connection, url: impl AsRef<str>, mut start: u64, mut duration: u64, filter: Option<&str>) -> Result<Input> {
 
     let audio_path = format!("{}{}.ogg", TMP_FORLDER, url.as_ref());
-    let json_path = format!("{}{}.json", TMP_FORLDER, url.as_ref());
 
-    let (audio, json) = (Path::new(&audio_path), Path::new(&json_path));
-    
-    let value = if audio.exists() && json.exists() {
-        // 파일이 있으면 파일에서 메타데이터 읽어옴
-        _metadata_from_file(json_path.to_owned()).await.unwrap()
+    let value = if Path::new(&audio_path).exists() {
+        // 받아둔 오디오가 있으면 메타데이터는 DB에서 읽어옴
+        ytdl_metadata(conn, url.as_ref()).await?
     } else {
-        // 파일이 없으면 ytdl로 다운받고 메타데이터 읽어옴
-        _metadata_from_ytdl(url.as_ref().to_owned(), audio_path.clone(), json_path).await.unwrap()
+        // 없으면 ytdl로 다운받으면서 메타데이터를 DB에 저장
+        let metadata = _metadata_from_ytdl(url.as_ref().to_owned(), audio_path.clone()).await?;
+        let _ = set_track_metadata(conn, url.as_ref().to_owned(), metadata.clone()).await;
+        metadata
     };
 
     let metadata = value.clone();
@@ -93,10 +103,15 @@ pub async fn ytdl_optioned(url: impl AsRef<str>, mut start: u64, mut duration: u
         duration = songbird_metadata.duration.unwrap().as_secs() - start;
     }
     
-    let mut ffmpeg = Command::new(FFMPEG_DL_COMMAND)
+    let mut ffmpeg = Command::new(FFMPEG_DL_COMMAND);
+    ffmpeg
         .args(&["-ss", start.to_string().as_str()])
         .args(&["-i", audio_path.as_str()])
-        .args(&["-t", duration.to_string().as_str()])
+        .args(&["-t", duration.to_string().as_str()]);
+    if let Some(filter) = filter {
+        ffmpeg.args(&["-af", filter]);
+    }
+    let mut ffmpeg = ffmpeg
         .args(&FFMPEG_ARGS)
         .arg("pipe:1")
         .stdout(Stdio::piped())
@@ -139,7 +154,44 @@ pub async fn ytdl_optioned(url: impl AsRef<str>, mut start: u64, mut duration: u
 
 }
 
-async fn _metadata_from_ytdl(url: String, audio_path: String, json_path: String) -> Result<Metadata> {
+// 재생하지 않고 메타데이터만 필요할 때 사용
+// DB에 없으면 오디오는 받지 않고 메타데이터만 받아서 저장 (DB에 없는 url은 저장되지 않음)
+pub async fn ytdl_metadata(conn: &Connection, url: impl AsRef<str>) -> Result<Metadata> {
+    if let Some(metadata) = track_metadata(conn, url.as_ref().to_owned()).await {
+        return Ok(metadata);
+    }
+
+    // 예전 버전이 오디오 옆에 남긴 json이 있으면 DB로 옮기고 지움
+    let json_path = format!("{}{}.json", TMP_FORLDER, url.as_ref());
+    let metadata = match _metadata_from_file(json_path.to_owned()).await {
+        Some(metadata) => {
+            let _ = tokio::fs::remove_file(json_path).await;
+            metadata
+        },
+        None => _metadata_only_from_ytdl(url.as_ref().to_owned()).await?,
+    };
+    let _ = set_track_metadata(conn, url.as_ref().to_owned(), metadata.clone()).await;
+    Ok(metadata)
+}
+
+// 곡을 추가할 때 재생 전에 메타데이터를 미리 채워둠
+// 유튜브 응답을 기다리지 않도록 백그라운드에서 하나씩 받음
+pub fn spawn_metadata_fill(conn: Connection, urls: Vec<String>) {
+    tokio::spawn(async move {
+        for url in urls {
+            if let Err(why) = ytdl_metadata(&conn, &url).await {
+                println!("failed to fetch metadata of {} {:?}", url, why);
+            }
+        }
+    });
+}
+
+// DB에서 곡이 지워지면 받아둔 파일도 정리
+pub async fn remove_cache(url: impl AsRef<str>) {
+    let _ = tokio::fs::remove_file(format!("{}{}.ogg", TMP_FORLDER, url.as_ref())).await;
+}
+
+async fn _metadata_from_ytdl(url: String, audio_path: String) -> Result<Metadata> {
     let mut youtube_dl = Command::new(YOUTUBE_DL_COMMAND)
         .args(&YTDL_COMMON_ARGS)
         .arg(url)
@@ -173,30 +225,32 @@ async fn _metadata_from_ytdl(url: String, audio_path: String, json_path: String)
     };
     
     let metadata = Metadata::from_ytdl_output(metadata.expect("ssibal"));
-    let metadata_clone = metadata.clone();
-
-    let handle = tokio::task::spawn(async move {
-        let mut json = TokioFile::create(json_path).await.unwrap();
-        let data = serde_json::to_string(&metadata_clone).unwrap();
-        json.write_all(data.as_bytes()).await.unwrap();
-        json.flush().await.unwrap();
-    });
 
     youtube_dl.wait().unwrap();
     ffmpeg.wait().unwrap();
-    handle.await.unwrap();
 
     Ok(metadata)
 }
 
-async fn _metadata_from_file(url: String) -> Result<Metadata> {
-    let mut json = File::open(url).expect("_metadata_from_file open error");
+async fn _metadata_only_from_ytdl(url: String) -> Result<Metadata> {
+    let output = TokioCommand::new(YOUTUBE_DL_COMMAND)
+        .args(&YTDL_METADATA_ARGS)
+        .arg(url)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await?;
 
-    let mut metadata_buf = vec![];
-    json.read_to_end(&mut metadata_buf).expect("json.read_to_end");
-    let metadata = serde_json::from_slice(&metadata_buf).unwrap();
+    let value: Value = serde_json::from_slice(&output.stdout).map_err(|err| Error::Json {
+        error: err,
+        parsed_text: std::str::from_utf8(&output.stdout).unwrap_or_default().to_string(),
+    })?;
+    Ok(Metadata::from_ytdl_output(value))
+}
 
-    Ok(metadata)
+async fn _metadata_from_file(path: String) -> Option<Metadata> {
+    let data = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&data).ok()
 }
 
 fn into_songbird_metadata(metadata: Metadata) -> SongbirdMetadata {
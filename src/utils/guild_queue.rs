@@ -8,9 +8,12 @@ use songbird::tracks::TrackHandle;
 
 use std::sync::Arc;
 use std::collections::VecDeque;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, mpsc::UnboundedSender};
 
-use crate::GuildQueueContainer;
+use crate::{
+    GuildQueueContainer,
+    quiz_handler::quiz_session::Guess,
+};
 pub struct GuildQueue {
     pub gid: GuildId,
 
@@ -28,6 +31,9 @@ pub struct GuildQueue {
 
     // keyword for skipping the current song
     pub skip_keyword: Option<Vec<String>>,
+
+    // sender for the answers of the running quiz
+    pub guess_sender: Option<UnboundedSender<Guess>>,
 }
 
 impl GuildQueue {
@@ -40,6 +46,7 @@ impl GuildQueue {
             url_queue: Box::new(VecDeque::new()),
             now_playing: None,
             skip_keyword: None,
+            guess_sender: None,
         }
     }
 
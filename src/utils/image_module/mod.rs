@@ -0,0 +1,2 @@
+pub mod thumbnail;
+pub mod pixelate;
\ No newline at end of file
@@ -0,0 +1,29 @@
+use std::io::Cursor;
+
+use image::{imageops::FilterType, DynamicImage, ImageOutputFormat};
+
+// 단계별 가로 픽셀 수 (앞쪽일수록 흐림)
+const PIXEL_WIDTHS: [u32; 5] = [6, 12, 24, 48, 96];
+
+// 썸네일을 점점 선명해지는 순서로 모자이크 처리한 PNG 목록
+// 마지막에는 원본을 넣음
+pub fn pixelated_stages(raw: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let original = image::load_from_memory(raw).ok()?;
+    let (width, height) = (original.width(), original.height());
+
+    let mut stages = vec![];
+    for pixel_width in PIXEL_WIDTHS.iter() {
+        let pixel_height = (height * pixel_width / width).max(1);
+        let small = original.resize_exact(*pixel_width, pixel_height, FilterType::Triangle);
+        let pixelated = small.resize_exact(width, height, FilterType::Nearest);
+        stages.push(encode_png(&pixelated)?);
+    }
+    stages.push(encode_png(&original)?);
+    Some(stages)
+}
+
+fn encode_png(image: &DynamicImage) -> Option<Vec<u8>> {
+    let mut cursor = Cursor::new(vec![]);
+    image.write_to(&mut cursor, ImageOutputFormat::Png).ok()?;
+    Some(cursor.into_inner())
+}
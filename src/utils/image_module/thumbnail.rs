@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use tokio::{
+    fs::{self, File as TokioFile},
+    io::AsyncWriteExt,
+};
+use tokio_rusqlite::Connection;
+
+use crate::utils::audio_module::youtube_dl::ytdl_metadata;
+
+const THUMBNAIL_FOLDER: &str = "./thumbnail/";
+
+// 썸네일 원본 바이트를 반환
+// ./thumbnail/에 받아둔 파일이 있으면 그대로 쓰고 없으면 받아서 저장
+pub async fn thumbnail(conn: &Connection, url: impl AsRef<str>) -> Option<Vec<u8>> {
+    let path = format!("{}{}.img", THUMBNAIL_FOLDER, url.as_ref());
+    if Path::new(&path).exists() {
+        return fs::read(&path).await.ok();
+    }
+
+    let metadata = ytdl_metadata(conn, url.as_ref()).await.ok()?;
+    let thumbnail_url = metadata.thumbnail?;
+    let data = reqwest::get(thumbnail_url).await.ok()?
+        .bytes().await.ok()?
+        .to_vec();
+
+    if fs::create_dir_all(THUMBNAIL_FOLDER).await.is_ok() {
+        if let Ok(mut file) = TokioFile::create(&path).await {
+            let _ = file.write_all(&data).await;
+        }
+    }
+    Some(data)
+}
+
+pub async fn remove_thumbnail(url: impl AsRef<str>) {
+    let _ = fs::remove_file(format!("{}{}.img", THUMBNAIL_FOLDER, url.as_ref())).await;
+}
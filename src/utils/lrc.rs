@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+// LRC 가사 한 줄
+#[derive(Clone, Debug)]
+pub struct LrcLine {
+    pub time: Duration,
+    pub text: String,
+}
+
+// [mm:ss.xx] 형식의 시간 태그를 읽어 시간순으로 정렬
+// 한 줄에 태그가 여러 개면 각 시간마다 같은 가사를 넣음
+// [ar:], [ti:] 같은 정보 태그는 무시하고 [offset:]만 반영
+pub fn parse_lrc(lrc: &str) -> Vec<LrcLine> {
+    let mut offset: i64 = 0;
+    let mut lines = vec![];
+
+    for raw in lrc.lines() {
+        let mut rest = raw.trim();
+        let mut times = vec![];
+        while let Some(tag_end) = rest.strip_prefix('[').and_then(|tag| tag.find(']')) {
+            let tag = &rest[1..tag_end + 1];
+            rest = rest[tag_end + 2..].trim_start();
+            if let Some(value) = tag.strip_prefix("offset:") {
+                offset = value.trim().parse().unwrap_or(0);
+            } else if let Some(time) = parse_time(tag) {
+                times.push(time);
+            }
+        }
+        for time in times {
+            lines.push((time, rest.to_owned()));
+        }
+    }
+
+    // offset이 양수면 가사가 더 빨리 나와야 함
+    let mut lines = lines.into_iter()
+        .map(|(time, text)| LrcLine {
+            time: Duration::from_millis((time - offset).max(0) as u64),
+            text,
+        })
+        .collect::<Vec<LrcLine>>();
+    lines.sort_by_key(|line| line.time);
+    lines
+}
+
+// mm:ss.xx 를 밀리초로 변환
+fn parse_time(tag: &str) -> Option<i64> {
+    let (minutes, seconds) = tag.split_once(':')?;
+    let minutes = minutes.trim().parse::<i64>().ok()?;
+    let seconds = seconds.trim().parse::<f64>().ok()?;
+    if seconds < 0.0 || seconds >= 60.0 {
+        return None;
+    }
+    Some(minutes * 60_000 + (seconds * 1000.0) as i64)
+}
+
+// 재생 위치 기준 현재 줄과 다음 줄
+pub fn lyric_at(lines: &[LrcLine], position: Duration) -> (Option<&str>, Option<&str>) {
+    let next_idx = lines.partition_point(|line| line.time <= position);
+    let current = next_idx.checked_sub(1).map(|idx| lines[idx].text.as_str());
+    let next = lines.get(next_idx).map(|line| line.text.as_str());
+    (current, next)
+}
@@ -1,4 +1,7 @@
 pub mod url_checker;
 pub mod audio_module;
+pub mod image_module;
 pub mod play_info_notifier;
-pub mod guild_queue;
\ No newline at end of file
+pub mod guild_queue;
+pub mod lrc;
+pub mod song_file;
@@ -60,6 +60,20 @@ pub fn update_play_info_embed(mut embed: CreateEmbed, title: &str, current: u64,
     embed
 }
 
+// 싱크 가사가 있으면 현재 줄은 굵게, 다음 줄은 그 아래에 표시
+pub fn update_play_info_embed_with_lyric(mut embed: CreateEmbed, title: &str, current: u64, total: u64, lyric: (Option<&str>, Option<&str>)) -> CreateEmbed {
+    let (current_line, next_line) = lyric;
+    let mut description = format!("{}\n{}/{}\n", title, duration_format(current), duration_format(total));
+    if let Some(line) = current_line.filter(|line| !line.is_empty()) {
+        description.push_str(&format!("\n**{}**", line));
+    }
+    if let Some(line) = next_line.filter(|line| !line.is_empty()) {
+        description.push_str(&format!("\n{}", line));
+    }
+    embed.description(description);
+    embed
+}
+
 fn duration_format(duration: u64) -> String {
     let seconds = duration;
     let minutes = seconds / 60;
@@ -0,0 +1,214 @@
+use serde::{Serialize, Deserialize};
+
+use crate::utils::url_checker::{url_checker, YOUTUBE_PREFIX};
+
+// 여러 값이 들어가는 CSV 칸(별칭, 태그)의 구분자
+const CSV_LIST_SEPARATOR: char = '|';
+
+// /구간설정과 같은 범위만 받음
+pub const MAX_CLIP_START: u64 = 3 * 60 * 60;
+pub const MAX_CLIP_DURATION: u64 = 60;
+
+// 곡 일괄 등록 파일의 한 줄
+// JSON은 이 구조체의 배열, CSV는 같은 이름의 헤더를 가진 표
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SongRow {
+    pub url: String,
+    pub title: String,
+    pub aliases: Vec<String>,
+    pub tags: Vec<String>,
+    pub artists: Vec<String>,
+    pub year: Option<i64>,
+    // 가사 빈칸 퀴즈용 가사와 재생 중 표시할 LRC 가사
+    pub lyrics: Option<String>,
+    pub lrc: Option<String>,
+    // 곡마다 정해 둔 출제 구간(초), 없으면 출제할 때 무작위로 정함
+    pub clip_start: Option<u64>,
+    pub clip_duration: Option<u64>,
+    // 출제된 횟수와 누군가 맞힌 횟수
+    pub played: i64,
+    pub solved: i64,
+}
+
+#[derive(Clone, Copy)]
+pub enum SongFileFormat {
+    Json,
+    Csv,
+}
+
+impl SongFileFormat {
+    pub fn from_filename(filename: &str) -> Option<SongFileFormat> {
+        let filename = filename.to_lowercase();
+        if filename.ends_with(".json") {
+            Some(SongFileFormat::Json)
+        } else if filename.ends_with(".csv") {
+            Some(SongFileFormat::Csv)
+        } else {
+            None
+        }
+    }
+}
+
+// 올바른 줄과 잘못된 줄의 번호(1부터, CSV는 헤더 다음 줄부터, JSON은 배열 순서)를 나눠서 반환
+// url은 video id로 바꿔서 돌려줌
+pub fn parse_song_file(format: SongFileFormat, data: &[u8]) -> Result<(Vec<SongRow>, Vec<usize>), String> {
+    let rows = match format {
+        SongFileFormat::Json => parse_json(data)?,
+        SongFileFormat::Csv => parse_csv(data)?,
+    };
+
+    let mut valid = vec![];
+    let mut invalid = vec![];
+    for (idx, row) in rows.into_iter().enumerate() {
+        match row.and_then(normalize_row) {
+            Some(row) => valid.push(row),
+            None => invalid.push(idx + 1),
+        }
+    }
+    Ok((valid, invalid))
+}
+
+// 배열이 아니면 파일 전체가 잘못된 것이고, 항목 하나가 잘못되면 그 항목만 건너뜀
+fn parse_json(data: &[u8]) -> Result<Vec<Option<SongRow>>, String> {
+    let values = serde_json::from_slice::<Vec<serde_json::Value>>(data)
+        .map_err(|why| format!("JSON 형식이 올바르지 않습니다. ({})", why))?;
+    let rows = values.into_iter()
+        .map(|value| serde_json::from_value::<SongRow>(value).ok())
+        .collect();
+    Ok(rows)
+}
+
+fn parse_csv(data: &[u8]) -> Result<Vec<Option<SongRow>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(data);
+    let headers = reader.headers()
+        .map_err(|_| "CSV 헤더를 읽을 수 없습니다.".to_owned())?
+        .clone();
+    let column = |name: &str| headers.iter().position(|header| header.eq_ignore_ascii_case(name));
+    let (url, title) = match (column("url"), column("title")) {
+        (Some(url), Some(title)) => (url, title),
+        _ => return Err("CSV에 url, title 열이 필요합니다.".to_owned()),
+    };
+    let (aliases, tags, artists) = (column("aliases"), column("tags"), column("artists"));
+    let (year, played, solved) = (column("year"), column("played"), column("solved"));
+    let (lyrics, lrc) = (column("lyrics"), column("lrc"));
+    let (clip_start, clip_duration) = (column("clip_start"), column("clip_duration"));
+
+    let rows = reader.records()
+        .map(|record| {
+            let record = record.ok()?;
+            let list = |idx: Option<usize>| idx
+                .and_then(|idx| record.get(idx))
+                .map(split_list)
+                .unwrap_or_default();
+            let text = |idx: Option<usize>| idx
+                .and_then(|idx| record.get(idx))
+                .filter(|field| !field.is_empty())
+                .map(str::to_owned);
+            // 숫자 칸은 비어 있어도 되지만 숫자가 아니면 잘못된 줄
+            let number = |idx: Option<usize>| match idx.and_then(|idx| record.get(idx)) {
+                Some(field) if !field.is_empty() => field.parse::<i64>().ok().map(Some),
+                _ => Some(None),
+            };
+            // 음수도 잘못된 줄
+            let seconds = |idx: Option<usize>| number(idx)?
+                .map(u64::try_from)
+                .transpose()
+                .ok();
+            Some(SongRow {
+                url: record.get(url)?.to_owned(),
+                title: record.get(title)?.to_owned(),
+                aliases: list(aliases),
+                tags: list(tags),
+                artists: list(artists),
+                year: number(year)?,
+                lyrics: text(lyrics),
+                lrc: text(lrc),
+                clip_start: seconds(clip_start)?,
+                clip_duration: seconds(clip_duration)?,
+                played: number(played)?.unwrap_or(0),
+                solved: number(solved)?.unwrap_or(0),
+            })
+        })
+        .collect();
+    Ok(rows)
+}
+
+fn split_list(field: &str) -> Vec<String> {
+    field.split(CSV_LIST_SEPARATOR)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+// url이 유효하지 않거나 제목이 비어 있거나 구간이 범위를 벗어나면 None
+fn normalize_row(row: SongRow) -> Option<SongRow> {
+    let url = url_checker(row.url.trim())?;
+    let title = row.title.trim().to_owned();
+    if title.is_empty() {
+        return None;
+    }
+    // 길이만 있는 구간은 쓸 수 없음
+    match (row.clip_start, row.clip_duration) {
+        (None, Some(_)) => return None,
+        (Some(start), _) if start > MAX_CLIP_START => return None,
+        (_, Some(duration)) if duration == 0 || duration > MAX_CLIP_DURATION => return None,
+        _ => (),
+    }
+    let trimmed = |items: Vec<String>| items.into_iter()
+        .map(|item| item.trim().to_owned())
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<String>>();
+    Some(SongRow {
+        url,
+        title,
+        aliases: trimmed(row.aliases),
+        tags: trimmed(row.tags),
+        artists: trimmed(row.artists),
+        year: row.year,
+        lyrics: row.lyrics.filter(|lyrics| !lyrics.trim().is_empty()),
+        lrc: row.lrc.filter(|lrc| !lrc.trim().is_empty()),
+        clip_start: row.clip_start,
+        clip_duration: row.clip_duration,
+        played: row.played.max(0),
+        solved: row.solved.clamp(0, row.played.max(0)),
+    })
+}
+
+// parse_song_file로 다시 읽을 수 있는 형식으로 씀
+// url은 url_checker를 통과하도록 전체 주소로 바꿈
+pub fn write_song_file(format: SongFileFormat, rows: Vec<SongRow>) -> Result<Vec<u8>, String> {
+    let rows = rows.into_iter()
+        .map(|row| SongRow { url: format!("{}{}", YOUTUBE_PREFIX, row.url), ..row })
+        .collect::<Vec<SongRow>>();
+    match format {
+        SongFileFormat::Json => serde_json::to_vec_pretty(&rows).map_err(|why| why.to_string()),
+        SongFileFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            let separator = CSV_LIST_SEPARATOR.to_string();
+            writer.write_record(["url", "title", "aliases", "tags", "artists", "year", "lyrics", "lrc", "clip_start", "clip_duration", "played", "solved"])
+                .map_err(|why| why.to_string())?;
+            for row in rows {
+                writer.write_record([
+                    row.url,
+                    row.title,
+                    row.aliases.join(&separator),
+                    row.tags.join(&separator),
+                    row.artists.join(&separator),
+                    row.year.map(|year| year.to_string()).unwrap_or_default(),
+                    row.lyrics.unwrap_or_default(),
+                    row.lrc.unwrap_or_default(),
+                    row.clip_start.map(|start| start.to_string()).unwrap_or_default(),
+                    row.clip_duration.map(|duration| duration.to_string()).unwrap_or_default(),
+                    row.played.to_string(),
+                    row.solved.to_string(),
+                ]).map_err(|why| why.to_string())?;
+            }
+            writer.into_inner().map_err(|why| why.to_string())
+        },
+    }
+}